@@ -0,0 +1,1111 @@
+//! Implements adaptors that wrap agents.
+use {
+    crate::{Agent, Consumer, Failure, Fault, Flawless, Flaws, Producer, Recall, TryBlame},
+    alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    },
+    core::{
+        cell::{Cell, RefCell},
+        convert::TryFrom,
+        fmt::{self, Debug, Display, Formatter},
+        marker::PhantomData,
+        num::NonZeroUsize,
+    },
+    fehler::{throw, throws},
+};
+#[cfg(feature = "std")]
+use {
+    core::{mem, time::Duration},
+    std::time::Instant,
+};
+
+/// Provides adaptors for a [`Consumer`].
+///
+/// Each adaptor is a [`Consumer`] with the same [`Flaws`](crate::Flaws) as the wrapped [`Consumer`] and displays as the wrapped [`Consumer`].
+pub trait ConsumerExt: Consumer {
+    /// Creates a [`Consumer`] that calls `m` on each good retrieved from `self`.
+    fn map<M, T>(self, m: M) -> Map<Self, M>
+    where
+        Self: Sized,
+        M: Fn(Self::Good) -> T,
+    {
+        Map { consumer: self, m }
+    }
+
+    /// Creates a [`Consumer`] that only retrieves the goods from `self` for which `predicate` returns `true`.
+    ///
+    /// Goods for which `predicate` returns `false` are dropped. If all goods currently available from `self` are dropped, the consumption throws the insufficiency thrown by `self`.
+    fn filter<P>(self, predicate: P) -> Filter<Self, P>
+    where
+        Self: Sized,
+        P: Fn(&Self::Good) -> bool,
+    {
+        Filter {
+            consumer: self,
+            predicate,
+        }
+    }
+
+    /// Creates a [`Consumer`] that retrieves the goods from `self` for which `m` returns [`Some`], retrieving the value within.
+    ///
+    /// Goods for which `m` returns [`None`] are dropped as they are by [`ConsumerExt::filter()`].
+    fn filter_map<M, T>(self, m: M) -> FilterMap<Self, M>
+    where
+        Self: Sized,
+        M: Fn(Self::Good) -> Option<T>,
+    {
+        FilterMap { consumer: self, m }
+    }
+
+    /// Creates a [`Consumer`] that calls `f` with a reference to each good retrieved from `self` before returning the good.
+    fn inspect<F>(self, f: F) -> Inspect<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&Self::Good),
+    {
+        Inspect { consumer: self, f }
+    }
+
+    /// Creates a [`Consumer`] that retrieves up to `max` goods from `self` at a time.
+    ///
+    /// Each consumption retrieves goods from `self` until `max` goods are retrieved or `self` throws a fault. If no goods are retrieved, the fault is thrown; otherwise the retrieved goods are returned, an insufficiency is dropped and a defect is thrown by the next consumption.
+    fn batched(self, max: NonZeroUsize) -> Batched<Self>
+    where
+        Self: Sized,
+    {
+        Batched {
+            consumer: self,
+            max: max.get(),
+            defect: RefCell::new(None),
+        }
+    }
+
+    /// Creates a [`Consumer`] that retrieves `max` goods from `self` at a time or whichever goods have been retrieved once `timeout` has elapsed.
+    ///
+    /// Goods retrieved from `self` are buffered across consumptions. A consumption retrieves the buffer once it holds `max` goods, once `timeout` has elapsed since the first good in the buffer was retrieved, or once `self` throws a defect; otherwise it throws the insufficiency thrown by `self`. A defect thrown while the buffer holds goods is thrown by the next consumption.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+    fn batched_timeout(self, max: NonZeroUsize, timeout: Duration) -> BatchedTimeout<Self>
+    where
+        Self: Sized,
+    {
+        BatchedTimeout {
+            consumer: self,
+            max: max.get(),
+            timeout,
+            buffer: RefCell::new(Vec::with_capacity(max.get())),
+            start: Cell::new(None),
+            defect: RefCell::new(None),
+        }
+    }
+}
+
+impl<C: Consumer> ConsumerExt for C {}
+
+/// A [`Consumer`] that maps the goods of another [`Consumer`].
+///
+/// Created by [`ConsumerExt::map()`].
+#[derive(Debug)]
+pub struct Map<C, M> {
+    /// The wrapped consumer.
+    consumer: C,
+    /// Maps each good.
+    m: M,
+}
+
+impl<C, M, T> Agent for Map<C, M>
+where
+    C: Consumer,
+    M: Fn(C::Good) -> T,
+{
+    type Good = T;
+}
+
+impl<C, M, T> Consumer for Map<C, M>
+where
+    C: Consumer,
+    // Required to forward demand to the wrapped consumer.
+    <C::Flaws as Flaws>::Defect: Flaws,
+    M: Fn(C::Good) -> T,
+{
+    type Flaws = C::Flaws;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        (self.m)(self.consumer.consume()?)
+    }
+
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good
+    where
+        <Self::Flaws as Flaws>::Defect: Flaws<Defect = <Self::Flaws as Flaws>::Defect>,
+        <<Self::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+            TryFrom<<Self::Flaws as Flaws>::Insufficiency>,
+    {
+        (self.m)(self.consumer.demand()?)
+    }
+}
+
+impl<C: Display, M> Display for Map<C, M> {
+    /// Writes the display of the wrapped consumer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.consumer)
+    }
+}
+
+/// A [`Consumer`] that filters the goods of another [`Consumer`].
+///
+/// Created by [`ConsumerExt::filter()`].
+#[derive(Debug)]
+pub struct Filter<C, P> {
+    /// The wrapped consumer.
+    consumer: C,
+    /// Returns if a good shall be retrieved.
+    predicate: P,
+}
+
+impl<C, P> Agent for Filter<C, P>
+where
+    C: Consumer,
+    P: Fn(&C::Good) -> bool,
+{
+    type Good = C::Good;
+}
+
+impl<C, P> Consumer for Filter<C, P>
+where
+    C: Consumer,
+    // Required to forward demand to the wrapped consumer.
+    <C::Flaws as Flaws>::Defect: Flaws,
+    P: Fn(&C::Good) -> bool,
+{
+    type Flaws = C::Flaws;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        loop {
+            let good = self.consumer.consume()?;
+
+            if (self.predicate)(&good) {
+                break good;
+            }
+        }
+    }
+
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good
+    where
+        <Self::Flaws as Flaws>::Defect: Flaws<Defect = <Self::Flaws as Flaws>::Defect>,
+        <<Self::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+            TryFrom<<Self::Flaws as Flaws>::Insufficiency>,
+    {
+        loop {
+            let good = self.consumer.demand()?;
+
+            if (self.predicate)(&good) {
+                break good;
+            }
+        }
+    }
+}
+
+impl<C: Display, P> Display for Filter<C, P> {
+    /// Writes the display of the wrapped consumer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.consumer)
+    }
+}
+
+/// A [`Consumer`] that filters and maps the goods of another [`Consumer`].
+///
+/// Created by [`ConsumerExt::filter_map()`].
+#[derive(Debug)]
+pub struct FilterMap<C, M> {
+    /// The wrapped consumer.
+    consumer: C,
+    /// Maps each good, returning [`None`] if the good shall not be retrieved.
+    m: M,
+}
+
+impl<C, M, T> Agent for FilterMap<C, M>
+where
+    C: Consumer,
+    M: Fn(C::Good) -> Option<T>,
+{
+    type Good = T;
+}
+
+impl<C, M, T> Consumer for FilterMap<C, M>
+where
+    C: Consumer,
+    // Required to forward demand to the wrapped consumer.
+    <C::Flaws as Flaws>::Defect: Flaws,
+    M: Fn(C::Good) -> Option<T>,
+{
+    type Flaws = C::Flaws;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        loop {
+            if let Some(good) = (self.m)(self.consumer.consume()?) {
+                break good;
+            }
+        }
+    }
+
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good
+    where
+        <Self::Flaws as Flaws>::Defect: Flaws<Defect = <Self::Flaws as Flaws>::Defect>,
+        <<Self::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+            TryFrom<<Self::Flaws as Flaws>::Insufficiency>,
+    {
+        loop {
+            if let Some(good) = (self.m)(self.consumer.demand()?) {
+                break good;
+            }
+        }
+    }
+}
+
+impl<C: Display, M> Display for FilterMap<C, M> {
+    /// Writes the display of the wrapped consumer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.consumer)
+    }
+}
+
+/// A [`Consumer`] that inspects the goods of another [`Consumer`].
+///
+/// Created by [`ConsumerExt::inspect()`].
+#[derive(Debug)]
+pub struct Inspect<C, F> {
+    /// The wrapped consumer.
+    consumer: C,
+    /// Inspects each good.
+    f: F,
+}
+
+impl<C, F> Agent for Inspect<C, F>
+where
+    C: Consumer,
+    F: Fn(&C::Good),
+{
+    type Good = C::Good;
+}
+
+impl<C, F> Consumer for Inspect<C, F>
+where
+    C: Consumer,
+    // Required to forward demand to the wrapped consumer.
+    <C::Flaws as Flaws>::Defect: Flaws,
+    F: Fn(&C::Good),
+{
+    type Flaws = C::Flaws;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        let good = self.consumer.consume()?;
+
+        (self.f)(&good);
+        good
+    }
+
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good
+    where
+        <Self::Flaws as Flaws>::Defect: Flaws<Defect = <Self::Flaws as Flaws>::Defect>,
+        <<Self::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+            TryFrom<<Self::Flaws as Flaws>::Insufficiency>,
+    {
+        let good = self.consumer.demand()?;
+
+        (self.f)(&good);
+        good
+    }
+}
+
+impl<C: Display, F> Display for Inspect<C, F> {
+    /// Writes the display of the wrapped consumer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.consumer)
+    }
+}
+
+/// A [`Consumer`] that retrieves batches of goods from another [`Consumer`].
+///
+/// Created by [`ConsumerExt::batched()`].
+pub struct Batched<C: Consumer> {
+    /// The wrapped consumer.
+    consumer: C,
+    /// The maximum number of goods in a batch.
+    max: usize,
+    /// The failure caused by a defect after goods were retrieved, thrown by the next consumption.
+    defect: RefCell<Option<Failure<C::Flaws>>>,
+}
+
+impl<C: Consumer> Agent for Batched<C> {
+    type Good = Vec<C::Good>;
+}
+
+impl<C: Consumer> Consumer for Batched<C> {
+    type Flaws = C::Flaws;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        if let Some(failure) = self.defect.borrow_mut().take() {
+            throw!(failure);
+        }
+
+        let mut batch = Vec::new();
+
+        while batch.len() < self.max {
+            match self.consumer.consume() {
+                Ok(good) => batch.push(good),
+                Err(failure) if batch.is_empty() => throw!(failure),
+                Err(failure) => {
+                    if failure.is_defect() {
+                        *self.defect.borrow_mut() = Some(failure);
+                    }
+
+                    break;
+                }
+            }
+        }
+
+        batch
+    }
+}
+
+impl<C: Consumer + Debug> Debug for Batched<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Batched")
+            .field("consumer", &self.consumer)
+            .field("max", &self.max)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Consumer + Display> Display for Batched<C> {
+    /// Writes the display of the wrapped consumer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.consumer)
+    }
+}
+
+/// A [`Consumer`] that retrieves batches of goods from another [`Consumer`], limited by size or time.
+///
+/// Created by [`ConsumerExt::batched_timeout()`].
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+pub struct BatchedTimeout<C: Consumer> {
+    /// The wrapped consumer.
+    consumer: C,
+    /// The maximum number of goods in a batch.
+    max: usize,
+    /// The time after which a partial batch is retrieved.
+    timeout: Duration,
+    /// The goods of the current batch.
+    buffer: RefCell<Vec<C::Good>>,
+    /// When the first good of the current batch was retrieved.
+    start: Cell<Option<Instant>>,
+    /// The failure caused by a defect while the buffer held goods, thrown by the next consumption.
+    defect: RefCell<Option<Failure<C::Flaws>>>,
+}
+
+#[cfg(feature = "std")]
+impl<C: Consumer> BatchedTimeout<C> {
+    /// Returns if the current batch shall be retrieved.
+    fn is_ready(&self, buffer: &[C::Good]) -> bool {
+        buffer.len() >= self.max
+            || self
+                .start
+                .get()
+                .map_or(false, |start| start.elapsed() >= self.timeout)
+    }
+
+    /// Removes and returns the current batch.
+    fn take(&self, buffer: &mut Vec<C::Good>) -> Vec<C::Good> {
+        self.start.set(None);
+        mem::replace(buffer, Vec::with_capacity(self.max))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: Consumer> Agent for BatchedTimeout<C> {
+    type Good = Vec<C::Good>;
+}
+
+#[cfg(feature = "std")]
+impl<C: Consumer> Consumer for BatchedTimeout<C> {
+    type Flaws = C::Flaws;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        if let Some(failure) = self.defect.borrow_mut().take() {
+            throw!(failure);
+        }
+
+        let mut buffer = self.buffer.borrow_mut();
+
+        let failure = loop {
+            if self.is_ready(&buffer) {
+                return self.take(&mut buffer);
+            }
+
+            match self.consumer.consume() {
+                Ok(good) => {
+                    if buffer.is_empty() {
+                        self.start.set(Some(Instant::now()));
+                    }
+
+                    buffer.push(good);
+                }
+                Err(failure) => break failure,
+            }
+        };
+
+        if failure.is_defect() && !buffer.is_empty() {
+            *self.defect.borrow_mut() = Some(failure);
+            self.take(&mut buffer)
+        } else if self.is_ready(&buffer) {
+            self.take(&mut buffer)
+        } else {
+            throw!(failure)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C> Debug for BatchedTimeout<C>
+where
+    C: Consumer + Debug,
+    C::Good: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchedTimeout")
+            .field("consumer", &self.consumer)
+            .field("max", &self.max)
+            .field("timeout", &self.timeout)
+            .field("buffer", &self.buffer)
+            .field("start", &self.start)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C: Consumer + Display> Display for BatchedTimeout<C> {
+    /// Writes the display of the wrapped consumer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.consumer)
+    }
+}
+
+/// Provides adaptors for a [`Producer`].
+///
+/// Each adaptor is a [`Producer`] with the same [`Flaws`](crate::Flaws) as the wrapped [`Producer`] and displays as the wrapped [`Producer`].
+pub trait ProducerExt: Producer {
+    /// Creates a [`Producer`] that stores each good of type `T` into `self` after converting it with `f`.
+    ///
+    /// `f` is given a reference so that the original good is contained in any [`Recall`] thrown by the adaptor.
+    fn map_input<F, T>(self, f: F) -> MapInput<Self, F, T>
+    where
+        Self: Sized,
+        F: Fn(&T) -> Self::Good,
+    {
+        MapInput {
+            producer: self,
+            f,
+            good: PhantomData,
+        }
+    }
+}
+
+impl<P: Producer> ProducerExt for P {}
+
+/// A [`Producer`] that converts goods before storing them into another [`Producer`].
+///
+/// Created by [`ProducerExt::map_input()`].
+#[derive(Debug)]
+pub struct MapInput<P, F, T> {
+    /// The wrapped producer.
+    producer: P,
+    /// Converts each good.
+    f: F,
+    /// The type of the goods.
+    good: PhantomData<fn(T)>,
+}
+
+impl<P, F, T> Agent for MapInput<P, F, T>
+where
+    P: Producer,
+    F: Fn(&T) -> P::Good,
+{
+    type Good = T;
+}
+
+impl<P: Display, F, T> Display for MapInput<P, F, T> {
+    /// Writes the display of the wrapped producer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.producer)
+    }
+}
+
+impl<P, F, T> Producer for MapInput<P, F, T>
+where
+    P: Producer,
+    // Required to forward force to the wrapped producer.
+    <P::Flaws as Flaws>::Defect: Flaws,
+    F: Fn(&T) -> P::Good,
+{
+    type Flaws = P::Flaws;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        if let Err(recall) = self.producer.produce((self.f)(&good)) {
+            throw!(Recall::new(recall.into_failure(), good));
+        }
+    }
+
+    #[throws(Recall<<Self::Flaws as Flaws>::Defect, Self::Good>)]
+    fn force(&self, good: Self::Good)
+    where
+        <Self::Flaws as Flaws>::Defect: Flaws<Defect = <Self::Flaws as Flaws>::Defect>,
+        <<Self::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+            TryFrom<<Self::Flaws as Flaws>::Insufficiency>,
+    {
+        if let Err(recall) = self.producer.force((self.f)(&good)) {
+            throw!(Recall::new(recall.into_failure(), good));
+        }
+    }
+}
+
+/// A [`Producer`] that stores a clone of each good into each of a list of [`Producer`]s.
+///
+/// Goods are stored into the branches in order. If a branch fails, the thrown [`Branch`] identifies the index of that branch; all branches before it have stored the good and all branches after it have not. [`Tee::produce_from()`] stores a recalled good into only the branches that have not stored it, which is how [`Producer::force()`] retries a good. Branches of different types are able to be combined as `Box<dyn Producer>`s.
+#[derive(Debug)]
+pub struct Tee<P> {
+    /// The name of the tee.
+    name: String,
+    /// The branches of the tee.
+    branches: Vec<P>,
+}
+
+impl<P> Tee<P> {
+    /// Creates a new [`Tee`] with `name` that stores each good into every producer in `branches`.
+    pub fn new<S>(name_str: &S, branches: Vec<P>) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self {
+            name: name_str.as_ref().to_string(),
+            branches,
+        }
+    }
+}
+
+impl<P> Tee<P>
+where
+    P: Producer,
+    P::Good: Clone,
+{
+    /// Stores `good` into each branch of `self` starting at the branch at `start`, skipping the branches before it.
+    ///
+    /// # Errors
+    ///
+    /// If a branch fails to store `good`, `produce_from` shall throw a [`Recall`] of the [`Branch`] at which the good shall be retried.
+    #[throws(Recall<TeeFlaws<P::Flaws>, P::Good>)]
+    pub fn produce_from(&self, start: usize, good: P::Good) {
+        for (index, branch) in self.branches.iter().enumerate().skip(start) {
+            if let Err(recall) = branch.produce(good.clone()) {
+                let fault = match recall.into_failure().into_fault() {
+                    Fault::Insufficiency(insufficiency) => {
+                        Fault::Insufficiency(Branch::new(index, insufficiency))
+                    }
+                    Fault::Defect(defect) => Fault::Defect(Branch::new(index, defect)),
+                };
+
+                throw!(self.recall(fault, good));
+            }
+        }
+    }
+}
+
+impl<P: Producer> Agent for Tee<P> {
+    type Good = P::Good;
+}
+
+impl<P> Display for Tee<P> {
+    /// Writes the name of the tee.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<P> Producer for Tee<P>
+where
+    P: Producer,
+    P::Good: Clone,
+{
+    type Flaws = TeeFlaws<P::Flaws>;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        self.produce_from(0, good)?;
+    }
+
+    #[throws(Recall<<Self::Flaws as Flaws>::Defect, Self::Good>)]
+    fn force(&self, mut good: Self::Good) {
+        let mut start = 0;
+
+        while let Err(recall) = self.produce_from(start, good) {
+            if let Fault::Insufficiency(ref branch) = *recall.failure().fault() {
+                start = branch.index();
+            }
+
+            match recall.try_blame() {
+                Ok(defect) => throw!(defect),
+                Err(error) => {
+                    good = error.into_good();
+                }
+            }
+        }
+    }
+}
+
+/// Specifies the [`Flaws`] of a [`Tee`] of [`Producer`]s with [`Flaws`] `F`.
+#[derive(Debug)]
+pub struct TeeFlaws<F> {
+    /// The flaws of the branches.
+    flaws: PhantomData<F>,
+}
+
+impl<F: Flaws> Flaws for TeeFlaws<F> {
+    type Insufficiency = Branch<F::Insufficiency>;
+    type Defect = Branch<F::Defect>;
+}
+
+/// A flaw thrown by a branch of a [`Tee`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Branch<T> {
+    /// The index of the branch that failed.
+    index: usize,
+    /// The flaw thrown by the branch.
+    flaw: T,
+}
+
+impl<T> Branch<T> {
+    /// Creates a new [`Branch`] of `flaw` thrown by the branch at `index`.
+    pub const fn new(index: usize, flaw: T) -> Self {
+        Self { index, flaw }
+    }
+
+    /// Returns the index of the branch that failed.
+    ///
+    /// All branches with a lower index have stored the good.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns a reference to the flaw thrown by the branch.
+    pub const fn flaw(&self) -> &T {
+        &self.flaw
+    }
+
+    /// Converts `self` into the flaw thrown by the branch.
+    #[allow(clippy::missing_const_for_fn)] // T may not be dropped in a const fn.
+    pub fn into_flaw(self) -> T {
+        self.flaw
+    }
+}
+
+impl<T: Display> Display for Branch<T> {
+    /// Writes "{flaw} in branch {index}".
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in branch {}", self.flaw, self.index)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+impl<T: Debug + Display> std::error::Error for Branch<T> {}
+
+impl<T> Flaws for Branch<T> {
+    type Insufficiency = Flawless;
+    type Defect = Self;
+}
+
+impl<I> TryFrom<Branch<I>> for Flawless {
+    type Error = ();
+
+    fn try_from(_: Branch<I>) -> Result<Self, Self::Error> {
+        Err(())
+    }
+}
+
+/// A [`Consumer`] that retrieves goods from a list of [`Consumer`]s.
+///
+/// Sources are visited in order, retrieving up to the weight of each source before moving to the next, so that no source is starved. Sources created by [`Merge::new()`] each have a weight of 1, resulting in round-robin retrieval.
+///
+/// A consumption only throws an insufficiency if every source throws a fault and at least one of them throws an insufficiency. A defect is only thrown once every source throws a defect, unless [`Merge::fail_fast()`] has been called, in which case the first defect is thrown.
+#[derive(Debug)]
+pub struct Merge<C> {
+    /// The name of the merge.
+    name: String,
+    /// The sources along with their weights; never empty.
+    sources: Vec<(C, usize)>,
+    /// The index of the source from which the next good is retrieved.
+    current: Cell<usize>,
+    /// The number of goods that may be retrieved from the current source before moving to the next.
+    credit: Cell<usize>,
+    /// If a defect from any source is thrown immediately.
+    is_fail_fast: bool,
+}
+
+impl<C> Merge<C> {
+    /// Creates a new [`Merge`] with `name` that retrieves from `sources` in round-robin order.
+    ///
+    /// # Errors
+    ///
+    /// If `sources` is empty, `new` shall throw [`NoSources`].
+    #[throws(NoSources)]
+    pub fn new<S>(name_str: &S, sources: Vec<C>) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self::with_sources(
+            name_str,
+            sources.into_iter().map(|source| (source, 1)).collect(),
+        )?
+    }
+
+    /// Creates a new [`Merge`] with `name` that retrieves up to the paired weight of goods from each source in turn.
+    ///
+    /// # Errors
+    ///
+    /// If `sources` is empty, `weighted` shall throw [`NoSources`].
+    #[throws(NoSources)]
+    pub fn weighted<S>(name_str: &S, sources: Vec<(C, NonZeroUsize)>) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self::with_sources(
+            name_str,
+            sources
+                .into_iter()
+                .map(|(source, weight)| (source, weight.get()))
+                .collect(),
+        )?
+    }
+
+    /// Creates a new [`Merge`] with `name` that retrieves from `sources`, each paired with a weight of at least 1.
+    #[throws(NoSources)]
+    fn with_sources<S>(name_str: &S, sources: Vec<(C, usize)>) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let credit = match sources.first() {
+            Some(&(_, weight)) => weight,
+            None => throw!(NoSources),
+        };
+
+        Self {
+            name: name_str.as_ref().to_string(),
+            sources,
+            current: Cell::new(0),
+            credit: Cell::new(credit),
+            is_fail_fast: false,
+        }
+    }
+
+    /// Sets `self` to throw the first defect thrown by any source.
+    #[must_use]
+    pub const fn fail_fast(mut self) -> Self {
+        self.is_fail_fast = true;
+        self
+    }
+
+    /// Returns the number of sources.
+    fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns the source at `index` along with its weight.
+    #[allow(clippy::indexing_slicing)] // Merge::with_sources() ensures sources is not empty and every index is less than its length.
+    fn source(&self, index: usize) -> &(C, usize) {
+        &self.sources[index]
+    }
+
+    /// Moves to the source after `index`.
+    fn advance(&self, index: usize) {
+        let next = index.saturating_add(1).checked_rem(self.len()).unwrap_or(0);
+
+        self.current.set(next);
+        self.credit.set(self.source(next).1);
+    }
+}
+
+impl<C: Consumer> Merge<C> {
+    /// Retrieves a good from the source at `index`, moving to the next source when the credit of that source is spent or it fails.
+    #[throws(Fault<C::Flaws>)]
+    fn consume_source(&self, index: usize) -> C::Good {
+        match self.source(index).0.consume() {
+            Ok(good) => {
+                let credit = self.credit.get().saturating_sub(1);
+
+                if credit == 0 {
+                    self.advance(index);
+                } else {
+                    self.credit.set(credit);
+                }
+
+                good
+            }
+            Err(failure) => {
+                self.advance(index);
+                throw!(failure.into_fault());
+            }
+        }
+    }
+}
+
+impl<C: Consumer> Agent for Merge<C> {
+    type Good = C::Good;
+}
+
+impl<C: Consumer> Consumer for Merge<C> {
+    type Flaws = C::Flaws;
+
+    #[allow(unreachable_code)] // Issue with fehler (#53) which has been resolved but not released.
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        let start = self.current.get();
+        // Merge always has at least one source, so the first attempt determines the initial fault.
+        let mut fault = match self.consume_source(start) {
+            Ok(good) => return good,
+            Err(fault) => fault,
+        };
+
+        for offset in 1..self.len() {
+            if fault.is_defect() && self.is_fail_fast {
+                break;
+            }
+
+            let index = start
+                .saturating_add(offset)
+                .checked_rem(self.len())
+                .unwrap_or(0);
+
+            match self.consume_source(index) {
+                Ok(good) => return good,
+                // An insufficiency takes precedence over a defect unless failing fast.
+                Err(next) => {
+                    if !next.is_defect() || fault.is_defect() || self.is_fail_fast {
+                        fault = next;
+                    }
+                }
+            }
+        }
+
+        throw!(self.failure(fault))
+    }
+}
+
+impl<C> Display for Merge<C> {
+    /// Writes the name of the merge.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// The error thrown when a [`Merge`] is created without any sources.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct NoSources;
+
+impl Display for NoSources {
+    /// Writes "merge requires at least one source".
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "merge requires at least one source")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+impl std::error::Error for NoSources {}
+
+/// A [`Producer`] that buffers goods and stores them as batches into another [`Producer`].
+///
+/// The buffer is flushed into the wrapped [`Producer`] when it holds `size` goods or when a good is produced at least `timeout` after the first buffered good. Since the deadline is only checked upon production, [`BatchingProducer::flush_if_due()`] may be called periodically to flush a stale buffer. Goods remaining in the buffer when the [`BatchingProducer`] is dropped are discarded without being flushed, so [`BatchingProducer::flush()`] should be called before dropping it.
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+pub struct BatchingProducer<P: Producer> {
+    /// The wrapped producer.
+    producer: P,
+    /// The number of goods that causes the buffer to be flushed.
+    size: NonZeroUsize,
+    /// The time after which a partial buffer is flushed.
+    timeout: Duration,
+    /// The goods that have not been flushed.
+    buffer: RefCell<P::Good>,
+    /// When the first good in the buffer was stored.
+    start: Cell<Option<Instant>>,
+    /// The failure of a flush after which the wrapped producer kept every good, which is thrown by the next production or flush.
+    failure: RefCell<Option<Failure<P::Flaws>>>,
+}
+
+#[cfg(feature = "std")]
+impl<P> Debug for BatchingProducer<P>
+where
+    P: Producer + Debug,
+    P::Good: Debug,
+    Failure<P::Flaws>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchingProducer")
+            .field("producer", &self.producer)
+            .field("size", &self.size)
+            .field("timeout", &self.timeout)
+            .field("buffer", &self.buffer)
+            .field("start", &self.start)
+            .field("failure", &self.failure)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P, G> BatchingProducer<P>
+where
+    P: Producer<Good = Vec<G>>,
+{
+    /// Creates a new [`BatchingProducer`] that stores batches of `size` goods, or the goods buffered for `timeout`, into `producer`.
+    ///
+    /// Goods still buffered when the [`BatchingProducer`] is dropped are discarded.
+    pub fn new(producer: P, size: NonZeroUsize, timeout: Duration) -> Self {
+        Self {
+            producer,
+            size,
+            timeout,
+            buffer: RefCell::new(Vec::with_capacity(size.get())),
+            start: Cell::new(None),
+            failure: RefCell::new(None),
+        }
+    }
+
+    /// Stores all buffered goods as a batch into the wrapped producer.
+    ///
+    /// # Errors
+    ///
+    /// If the wrapped producer fails to store the batch, `flush` shall throw the [`Recall`] of the wrapped producer, which contains the unflushed batch. The buffer is empty afterwards.
+    ///
+    /// If a previous production failed to flush but the wrapped producer kept every good, `flush` shall throw that failure with an empty batch and leave the buffer unchanged.
+    #[throws(Recall<P::Flaws, Vec<G>>)]
+    pub fn flush(&self) {
+        if let Some(failure) = self.failure.borrow_mut().take() {
+            throw!(Recall::new(failure, Vec::new()));
+        }
+
+        let batch = self.take();
+
+        if !batch.is_empty() {
+            self.producer.produce(batch)?;
+        }
+    }
+
+    /// Flushes the buffer if `timeout` has elapsed since the first buffered good was stored.
+    ///
+    /// # Errors
+    ///
+    /// Throws the same as [`BatchingProducer::flush()`].
+    #[throws(Recall<P::Flaws, Vec<G>>)]
+    pub fn flush_if_due(&self) {
+        if self.is_due() {
+            self.flush()?;
+        }
+    }
+
+    /// Returns if `timeout` has elapsed since the first buffered good was stored.
+    fn is_due(&self) -> bool {
+        self.start
+            .get()
+            .map_or(false, |start| start.elapsed() >= self.timeout)
+    }
+
+    /// Removes and returns the buffered goods.
+    fn take(&self) -> Vec<G> {
+        self.start.set(None);
+        mem::replace(
+            &mut *self.buffer.borrow_mut(),
+            Vec::with_capacity(self.size.get()),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P, G> Agent for BatchingProducer<P>
+where
+    P: Producer<Good = Vec<G>>,
+{
+    type Good = G;
+}
+
+#[cfg(feature = "std")]
+impl<P: Producer + Display> Display for BatchingProducer<P> {
+    /// Writes the display of the wrapped producer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.producer)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<P, G> Producer for BatchingProducer<P>
+where
+    P: Producer<Good = Vec<G>>,
+{
+    type Flaws = P::Flaws;
+
+    /// Buffers `good`, flushing the buffer if it is full or due.
+    ///
+    /// If the flush fails, the buffer is restored from the recalled batch without its last good and that good is recalled. If the recalled batch holds no goods, the wrapped producer has kept every good, including `good`, so there is no good to recall; the failure is instead thrown by the next production, which recalls the good it was given, or the next flush.
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        if let Some(failure) = self.failure.borrow_mut().take() {
+            throw!(Recall::new(failure, good));
+        }
+
+        {
+            let mut buffer = self.buffer.borrow_mut();
+
+            if buffer.is_empty() {
+                self.start.set(Some(Instant::now()));
+            }
+
+            buffer.push(good);
+
+            if buffer.len() < self.size.get() && !self.is_due() {
+                return;
+            }
+        }
+
+        let start = self.start.get();
+
+        if let Err(recall) = self.flush() {
+            let (failure, mut batch) = recall.into_parts();
+
+            match batch.pop() {
+                Some(good) => {
+                    if !batch.is_empty() {
+                        self.start.set(start);
+                    }
+
+                    *self.buffer.borrow_mut() = batch;
+                    throw!(Recall::new(failure, good));
+                }
+                None => {
+                    let _ = self.failure.borrow_mut().replace(failure);
+                }
+            }
+        }
+    }
+}
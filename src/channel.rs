@@ -0,0 +1,110 @@
+//! Defines traits of markets for a channel.
+//!
+//! A channel exchanges goods between [`Producer`]s and [`Consumer`]s. If either all [`Consumer`]s or all [`Producer`]s for a channel are dropped, the channel becomes invalid.
+#[cfg(feature = "std")]
+mod broadcast;
+#[cfg(feature = "std")]
+mod finite;
+#[cfg(feature = "std")]
+mod infinite;
+#[cfg(feature = "alloc")]
+mod lockfree;
+#[cfg(feature = "alloc")]
+mod mpmc;
+#[cfg(feature = "std")]
+mod oneshot;
+#[cfg(feature = "alloc")]
+mod spsc;
+#[cfg(feature = "std")]
+mod watch;
+
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+pub use {
+    broadcast::{BroadcastChannel, BroadcastConsumer, BroadcastDefect, BroadcastProducer, Lagged},
+    finite::{StdFiniteChannel, StdFiniteConsumer, StdFiniteProducer},
+    infinite::{StdInfiniteChannel, StdInfiniteConsumer, StdInfiniteProducer},
+    oneshot::{OneshotChannel, OneshotConsumer, OneshotDefect, OneshotProducer, Spent},
+    watch::{WatchChannel, WatchConsumer, WatchProducer},
+};
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "alloc")))]
+pub use {
+    mpmc::{MpmcChannel, MpmcConsumer, MpmcProducer},
+    spsc::{SpscChannel, SpscConsumer, SpscProducer},
+};
+
+use {
+    super::{Consumer, ConsumptionFlaws, Flawless, Flaws, Producer, ProductionFlaws},
+    core::fmt::{self, Display, Formatter},
+};
+
+/// The defect thrown when a [`Producer`] attempts to produce to a channel with no [`Consumer`]s.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct WithdrawnDemand;
+
+impl Display for WithdrawnDemand {
+    /// Writes "demand has withdrawn".
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "demand has withdrawn")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+impl std::error::Error for WithdrawnDemand {}
+
+impl Flaws for WithdrawnDemand {
+    type Insufficiency = Flawless;
+    type Defect = Self;
+}
+
+/// The defect thrown when a [`Consumer`] attempts to consume from an empty channel with no [`Producer`]s.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct WithdrawnSupply;
+
+impl Display for WithdrawnSupply {
+    /// Writes "supply has withdrawn".
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "supply has withdrawn")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+impl std::error::Error for WithdrawnSupply {}
+
+impl Flaws for WithdrawnSupply {
+    type Insufficiency = Flawless;
+    type Defect = Self;
+}
+
+/// Characterizes a channel with infinite capacity.
+pub trait InfiniteChannel<G> {
+    /// Specifies the [`Producer`].
+    type Producer: Producer<Good = G, Flaws = WithdrawnDemand>;
+    /// Specifies the [`Consumer`].
+    type Consumer: Consumer<Good = G, Flaws = ConsumptionFlaws<WithdrawnSupply>>;
+
+    /// Creates the [`Producer`] and [`Consumer`] connected to an infinite channel.
+    fn establish<S>(name_str: &S) -> (Self::Producer, Self::Consumer)
+    where
+        S: AsRef<str> + ?Sized;
+}
+
+/// Characterizes a channel with a limited capacity.
+pub trait FiniteChannel<G> {
+    /// Specifies the [`Producer`].
+    type Producer: Producer<Good = G, Flaws = ProductionFlaws<WithdrawnDemand>>;
+    /// Specifies the [`Consumer`].
+    type Consumer: Consumer<Good = G, Flaws = ConsumptionFlaws<WithdrawnSupply>>;
+
+    /// Creates the [`Producer`] and [`Consumer`] connected to a channel with capacity of `size`.
+    ///
+    /// If `size` is 0, the channel shall be a rendezvous channel: the [`Producer`] shall only store a good while a [`Consumer`] is waiting in [`Consumer::demand`], otherwise throwing [`FullStock`](crate::FullStock). As a result, [`Producer::force`] blocks until the good is handed off to a [`Consumer`].
+    fn establish<S>(name_str: &S, size: usize) -> (Self::Producer, Self::Consumer)
+    where
+        S: AsRef<str> + ?Sized;
+}
@@ -0,0 +1,355 @@
+//! Implements a channel that delivers every good to every [`Consumer`].
+use {
+    super::{WithdrawnDemand, WithdrawnSupply},
+    crate::{
+        sync::lock, Agent, Consumer, ConsumptionFlaws, EmptyStock, Failure, Fault, Flawless, Flaws,
+        Producer, Recall,
+    },
+    core::{
+        cell::Cell,
+        convert::TryFrom,
+        fmt::{self, Display, Formatter},
+    },
+    fehler::{throw, throws},
+    std::{
+        collections::VecDeque,
+        string::{String, ToString},
+        sync::{Arc, Condvar, Mutex, PoisonError},
+    },
+};
+
+/// A channel where each [`Consumer`] retrieves every good stored after it was created.
+///
+/// The channel retains the most recent `size` goods. Storing a good into a full channel overwrites the oldest good, so a [`Producer`] is never blocked by a slow [`Consumer`]; instead, a [`Consumer`] that has not retrieved an overwritten good throws [`Lagged`] and resumes from the oldest retained good.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct BroadcastChannel;
+
+impl BroadcastChannel {
+    /// Creates the [`Producer`] and [`Consumer`] connected to a broadcast channel that retains `size` goods.
+    pub fn establish<G, S>(
+        name_str: &S,
+        size: usize,
+    ) -> (BroadcastProducer<G>, BroadcastConsumer<G>)
+    where
+        G: Clone,
+        S: AsRef<str> + ?Sized,
+    {
+        let name = name_str.as_ref().to_string();
+        let market = Arc::new(Market {
+            stock: Mutex::new(Stock {
+                goods: VecDeque::with_capacity(size),
+                size,
+                start: 0,
+                producer_count: 1,
+                consumer_count: 1,
+            }),
+            has_goods: Condvar::new(),
+        });
+
+        (
+            BroadcastProducer {
+                name: name.clone(),
+                market: Arc::clone(&market),
+            },
+            BroadcastConsumer {
+                name,
+                market,
+                cursor: Cell::new(0),
+            },
+        )
+    }
+}
+
+/// The defect thrown when a [`Consumer`] of a [`BroadcastChannel`] attempts to retrieve a good that has been overwritten.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lagged {
+    /// The number of goods that were overwritten before being retrieved.
+    skipped: usize,
+}
+
+impl Lagged {
+    /// Returns the number of goods that were overwritten before being retrieved.
+    #[must_use]
+    pub const fn skipped(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl Display for Lagged {
+    /// Writes "lagged behind by {skipped} goods".
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "lagged behind by {} goods", self.skipped)
+    }
+}
+
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+impl std::error::Error for Lagged {}
+
+impl Flaws for Lagged {
+    type Insufficiency = Flawless;
+    type Defect = Self;
+}
+
+/// The defect thrown when a [`Consumer`] of a [`BroadcastChannel`] fails to retrieve a good.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum BroadcastDefect {
+    /// The [`Consumer`] did not retrieve goods before they were overwritten.
+    Lagged(Lagged),
+    /// The channel is empty and all [`Producer`]s have been dropped.
+    WithdrawnSupply(WithdrawnSupply),
+}
+
+impl Display for BroadcastDefect {
+    /// Writes the display of the defect.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Lagged(ref lagged) => write!(f, "{}", lagged),
+            Self::WithdrawnSupply(ref withdrawn_supply) => write!(f, "{}", withdrawn_supply),
+        }
+    }
+}
+
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+impl std::error::Error for BroadcastDefect {}
+
+impl Flaws for BroadcastDefect {
+    type Insufficiency = Flawless;
+    type Defect = Self;
+}
+
+impl From<Lagged> for BroadcastDefect {
+    fn from(lagged: Lagged) -> Self {
+        Self::Lagged(lagged)
+    }
+}
+
+impl From<WithdrawnSupply> for BroadcastDefect {
+    fn from(withdrawn_supply: WithdrawnSupply) -> Self {
+        Self::WithdrawnSupply(withdrawn_supply)
+    }
+}
+
+/// The state shared by the agents of a [`BroadcastChannel`].
+#[derive(Debug)]
+struct Market<G> {
+    /// The stock of the channel.
+    stock: Mutex<Stock<G>>,
+    /// Notified when a good is stored or a [`Producer`] is dropped.
+    has_goods: Condvar,
+}
+
+/// The goods retained by a [`BroadcastChannel`] along with the state of its agents.
+#[derive(Debug)]
+struct Stock<G> {
+    /// The retained goods in the order they were stored.
+    goods: VecDeque<G>,
+    /// The maximum number of retained goods.
+    size: usize,
+    /// The sequence number of the oldest retained good.
+    start: u64,
+    /// The number of [`Producer`]s connected to the channel.
+    producer_count: usize,
+    /// The number of [`Consumer`]s connected to the channel.
+    consumer_count: usize,
+}
+
+impl<G> Stock<G> {
+    /// Returns the sequence number of the next good to be stored.
+    fn end(&self) -> u64 {
+        self.start
+            .saturating_add(u64::try_from(self.goods.len()).unwrap_or(u64::MAX))
+    }
+}
+
+/// The [`Producer`] of a [`BroadcastChannel`].
+#[derive(Debug)]
+pub struct BroadcastProducer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The market of the channel.
+    market: Arc<Market<G>>,
+}
+
+impl<G> BroadcastProducer<G> {
+    /// Creates a new [`Consumer`] that retrieves every good stored after this call.
+    #[must_use]
+    pub fn subscribe(&self) -> BroadcastConsumer<G> {
+        let mut stock = lock(&self.market.stock);
+
+        stock.consumer_count = stock.consumer_count.saturating_add(1);
+        BroadcastConsumer {
+            name: self.name.clone(),
+            market: Arc::clone(&self.market),
+            cursor: Cell::new(stock.end()),
+        }
+    }
+}
+
+impl<G> Agent for BroadcastProducer<G> {
+    type Good = G;
+}
+
+impl<G> Clone for BroadcastProducer<G> {
+    fn clone(&self) -> Self {
+        let mut stock = lock(&self.market.stock);
+
+        stock.producer_count = stock.producer_count.saturating_add(1);
+
+        Self {
+            name: self.name.clone(),
+            market: Arc::clone(&self.market),
+        }
+    }
+}
+
+impl<G> Display for BroadcastProducer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Drop for BroadcastProducer<G> {
+    fn drop(&mut self) {
+        let mut stock = lock(&self.market.stock);
+
+        stock.producer_count = stock.producer_count.saturating_sub(1);
+        self.market.has_goods.notify_all();
+    }
+}
+
+impl<G> Producer for BroadcastProducer<G> {
+    type Flaws = WithdrawnDemand;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        let mut stock = lock(&self.market.stock);
+
+        if stock.consumer_count == 0 {
+            throw!(self.recall(Fault::Defect(WithdrawnDemand), good));
+        }
+
+        if stock.size == 0 {
+            // The good is immediately overwritten.
+            stock.start = stock.start.saturating_add(1);
+        } else {
+            if stock.goods.len() == stock.size {
+                let _ = stock.goods.pop_front();
+                stock.start = stock.start.saturating_add(1);
+            }
+
+            stock.goods.push_back(good);
+        }
+
+        self.market.has_goods.notify_all();
+    }
+}
+
+/// The [`Consumer`] of a [`BroadcastChannel`].
+///
+/// Each [`BroadcastConsumer`] tracks its own position in the channel; a clone resumes from the same position as the original.
+#[derive(Debug)]
+pub struct BroadcastConsumer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The market of the channel.
+    market: Arc<Market<G>>,
+    /// The sequence number of the next good to be retrieved.
+    cursor: Cell<u64>,
+}
+
+impl<G: Clone> BroadcastConsumer<G> {
+    /// Retrieves the next good from `stock`, returning [`None`] if no good is available and the channel has a [`Producer`].
+    fn take(&self, stock: &Stock<G>) -> Option<Result<G, BroadcastDefect>> {
+        let cursor = self.cursor.get();
+
+        if cursor < stock.start {
+            self.cursor.set(stock.start);
+            Some(Err(BroadcastDefect::Lagged(Lagged {
+                skipped: usize::try_from(stock.start.saturating_sub(cursor)).unwrap_or(usize::MAX),
+            })))
+        } else if let Some(good) = usize::try_from(cursor.saturating_sub(stock.start))
+            .ok()
+            .and_then(|index| stock.goods.get(index))
+        {
+            self.cursor.set(cursor.saturating_add(1));
+            Some(Ok(good.clone()))
+        } else if stock.producer_count == 0 {
+            Some(Err(BroadcastDefect::WithdrawnSupply(WithdrawnSupply)))
+        } else {
+            None
+        }
+    }
+}
+
+impl<G> Agent for BroadcastConsumer<G> {
+    type Good = G;
+}
+
+impl<G> Clone for BroadcastConsumer<G> {
+    fn clone(&self) -> Self {
+        let mut stock = lock(&self.market.stock);
+
+        stock.consumer_count = stock.consumer_count.saturating_add(1);
+
+        Self {
+            name: self.name.clone(),
+            market: Arc::clone(&self.market),
+            cursor: self.cursor.clone(),
+        }
+    }
+}
+
+impl<G: Clone> Consumer for BroadcastConsumer<G> {
+    type Flaws = ConsumptionFlaws<BroadcastDefect>;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.take(&lock(&self.market.stock)) {
+            Some(Ok(good)) => good,
+            Some(Err(defect)) => throw!(self.failure(Fault::Defect(defect))),
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+
+    /// Retrieves the next good from the channel, blocking until one is available.
+    ///
+    /// # Errors
+    ///
+    /// If `self` has lagged behind or the channel is empty and all [`Producer`]s have been dropped, `demand` shall throw the appropriate [`BroadcastDefect`].
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good {
+        let mut stock = lock(&self.market.stock);
+
+        loop {
+            match self.take(&stock) {
+                Some(Ok(good)) => break good,
+                Some(Err(defect)) => throw!(Failure::new(self, Fault::Defect(defect))),
+                None => {
+                    stock = self
+                        .market
+                        .has_goods
+                        .wait(stock)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+            }
+        }
+    }
+}
+
+impl<G> Display for BroadcastConsumer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Drop for BroadcastConsumer<G> {
+    fn drop(&mut self) {
+        let mut stock = lock(&self.market.stock);
+
+        stock.consumer_count = stock.consumer_count.saturating_sub(1);
+    }
+}
@@ -0,0 +1,310 @@
+//! Implements [`FiniteChannel`] using a [`Mutex`] and [`Condvar`]s.
+use {
+    super::{FiniteChannel, WithdrawnDemand, WithdrawnSupply},
+    crate::{
+        queue::Overflow, sync::lock, Agent, Consumer, ConsumptionFlaws, EmptyStock, Failure, Fault,
+        Flaws, FullStock, Producer, ProductionFlaws, Recall,
+    },
+    core::fmt::{self, Display, Formatter},
+    fehler::{throw, throws},
+    std::{
+        collections::VecDeque,
+        string::{String, ToString},
+        sync::{Arc, Condvar, Mutex, PoisonError},
+    },
+};
+
+/// A [`FiniteChannel`] backed by a [`Mutex`] protecting a ring of goods.
+///
+/// The [`Producer`] of the channel may be cloned to allow multiple [`Producer`]s to store goods into the same channel.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct StdFiniteChannel;
+
+impl StdFiniteChannel {
+    /// Creates the [`Producer`] and [`Consumer`] connected to a channel of `size` that handles a good stored while full as defined by `overflow`.
+    ///
+    /// Goods dropped due to overflow are dropped within the channel.
+    pub fn establish_with_overflow<G, S>(
+        name_str: &S,
+        size: usize,
+        overflow: Overflow,
+    ) -> (StdFiniteProducer<G>, StdFiniteConsumer<G>)
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let name = name_str.as_ref().to_string();
+        let market = Arc::new(Market {
+            stock: Mutex::new(Stock {
+                goods: VecDeque::with_capacity(size),
+                size,
+                overflow,
+                producer_count: 1,
+                has_consumer: true,
+                demand_count: 0,
+            }),
+            has_goods: Condvar::new(),
+            has_space: Condvar::new(),
+        });
+
+        (
+            StdFiniteProducer {
+                name: name.clone(),
+                market: Arc::clone(&market),
+            },
+            StdFiniteConsumer { name, market },
+        )
+    }
+}
+
+impl<G> FiniteChannel<G> for StdFiniteChannel {
+    type Producer = StdFiniteProducer<G>;
+    type Consumer = StdFiniteConsumer<G>;
+
+    fn establish<S>(name_str: &S, size: usize) -> (Self::Producer, Self::Consumer)
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self::establish_with_overflow(name_str, size, Overflow::Reject)
+    }
+}
+
+/// The state shared by the [`Producer`]s and [`Consumer`] of a [`StdFiniteChannel`].
+#[derive(Debug)]
+struct Market<G> {
+    /// The stock of the channel.
+    stock: Mutex<Stock<G>>,
+    /// Notified when a good is stored or a [`Producer`] is dropped.
+    has_goods: Condvar,
+    /// Notified when a good is retrieved or the [`Consumer`] is dropped.
+    has_space: Condvar,
+}
+
+/// The goods stored in a [`StdFiniteChannel`] along with the state of its agents.
+#[derive(Debug)]
+struct Stock<G> {
+    /// The goods in the order they were stored.
+    goods: VecDeque<G>,
+    /// The maximum number of goods.
+    size: usize,
+    /// How a good stored while the channel is full is handled.
+    overflow: Overflow,
+    /// The number of [`Producer`]s connected to the channel.
+    producer_count: usize,
+    /// If the [`Consumer`] is connected to the channel.
+    has_consumer: bool,
+    /// The number of [`Consumer`]s blocked in [`Consumer::demand`].
+    demand_count: usize,
+}
+
+impl<G> Stock<G> {
+    /// Returns if `self` is able to store another good.
+    ///
+    /// A rendezvous channel is only able to store a good for each [`Consumer`] that is waiting for one.
+    fn has_space(&self) -> bool {
+        if self.size == 0 {
+            self.goods.len() < self.demand_count
+        } else {
+            self.goods.len() < self.size
+        }
+    }
+
+    /// Stores `good` if `self` has space, otherwise handles `good` as defined by the overflow of `self`.
+    ///
+    /// Returns `good` if it is rejected.
+    fn store(&mut self, good: G) -> Result<(), G> {
+        if self.has_space() {
+            self.goods.push_back(good);
+        } else {
+            match self.overflow {
+                Overflow::Reject => return Err(good),
+                Overflow::DropNewest => {}
+                Overflow::DropOldest => {
+                    // A channel without any goods has no oldest good to evict.
+                    if self.goods.pop_front().is_some() {
+                        self.goods.push_back(good);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The [`Producer`] of a [`StdFiniteChannel`].
+#[derive(Debug)]
+pub struct StdFiniteProducer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The market of the channel.
+    market: Arc<Market<G>>,
+}
+
+impl<G> Agent for StdFiniteProducer<G> {
+    type Good = G;
+}
+
+impl<G> Clone for StdFiniteProducer<G> {
+    fn clone(&self) -> Self {
+        let mut stock = lock(&self.market.stock);
+
+        stock.producer_count = stock.producer_count.saturating_add(1);
+
+        Self {
+            name: self.name.clone(),
+            market: Arc::clone(&self.market),
+        }
+    }
+}
+
+impl<G> Display for StdFiniteProducer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Drop for StdFiniteProducer<G> {
+    fn drop(&mut self) {
+        let mut stock = lock(&self.market.stock);
+
+        stock.producer_count = stock.producer_count.saturating_sub(1);
+        self.market.has_goods.notify_all();
+    }
+}
+
+impl<G> Producer for StdFiniteProducer<G> {
+    type Flaws = ProductionFlaws<WithdrawnDemand>;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        let mut stock = lock(&self.market.stock);
+
+        if !stock.has_consumer {
+            throw!(self.recall(Fault::Defect(WithdrawnDemand), good));
+        }
+
+        if let Err(good) = stock.store(good) {
+            throw!(self.recall(Fault::Insufficiency(FullStock), good));
+        }
+
+        self.market.has_goods.notify_one();
+    }
+
+    /// Stores `good` into the channel, blocking until stock is available.
+    ///
+    /// If the channel was established with an [`Overflow`] other than [`Overflow::Reject`], `force` does not block.
+    ///
+    /// # Errors
+    ///
+    /// If the [`Consumer`] has been dropped, `force` shall throw a [`Recall`] containing [`WithdrawnDemand`] and `good`.
+    #[throws(Recall<<Self::Flaws as Flaws>::Defect, Self::Good>)]
+    fn force(&self, good: Self::Good) {
+        let mut stock = lock(&self.market.stock);
+
+        loop {
+            if !stock.has_consumer {
+                throw!(Recall::new(
+                    Failure::new(self, Fault::Defect(WithdrawnDemand)),
+                    good
+                ));
+            }
+
+            if stock.has_space() || stock.overflow != Overflow::Reject {
+                break;
+            }
+
+            stock = self
+                .market
+                .has_space
+                .wait(stock)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+
+        // The good is not rejected since either stock has space or overflow is not Reject.
+        let _ = stock.store(good);
+        self.market.has_goods.notify_one();
+    }
+}
+
+/// The [`Consumer`] of a [`StdFiniteChannel`].
+#[derive(Debug)]
+pub struct StdFiniteConsumer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The market of the channel.
+    market: Arc<Market<G>>,
+}
+
+impl<G> Agent for StdFiniteConsumer<G> {
+    type Good = G;
+}
+
+impl<G> Consumer for StdFiniteConsumer<G> {
+    type Flaws = ConsumptionFlaws<WithdrawnSupply>;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        let mut stock = lock(&self.market.stock);
+
+        match stock.goods.pop_front() {
+            Some(good) => {
+                self.market.has_space.notify_one();
+                good
+            }
+            None if stock.producer_count == 0 => {
+                throw!(self.failure(Fault::Defect(WithdrawnSupply)))
+            }
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+
+    /// Retrieves the next good from the channel, blocking until one is available.
+    ///
+    /// # Errors
+    ///
+    /// If the channel is empty and all [`Producer`]s have been dropped, `demand` shall throw a [`WithdrawnSupply`].
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good {
+        let mut stock = lock(&self.market.stock);
+
+        stock.demand_count = stock.demand_count.saturating_add(1);
+        // A rendezvous channel now has space for a good.
+        self.market.has_space.notify_one();
+
+        let outcome = loop {
+            if let Some(good) = stock.goods.pop_front() {
+                self.market.has_space.notify_one();
+                break Ok(good);
+            }
+
+            if stock.producer_count == 0 {
+                break Err(Failure::new(self, Fault::Defect(WithdrawnSupply)));
+            }
+
+            stock = self
+                .market
+                .has_goods
+                .wait(stock)
+                .unwrap_or_else(PoisonError::into_inner);
+        };
+
+        stock.demand_count = stock.demand_count.saturating_sub(1);
+        outcome?
+    }
+}
+
+impl<G> Display for StdFiniteConsumer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Drop for StdFiniteConsumer<G> {
+    fn drop(&mut self) {
+        lock(&self.market.stock).has_consumer = false;
+        self.market.has_space.notify_all();
+    }
+}
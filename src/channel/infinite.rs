@@ -0,0 +1,127 @@
+//! Implements [`InfiniteChannel`] using [`std::sync::mpsc`].
+use {
+    super::{InfiniteChannel, WithdrawnDemand, WithdrawnSupply},
+    crate::{
+        Agent, Consumer, ConsumptionFlaws, EmptyStock, Failure, Fault, Flaws, Producer, Recall,
+    },
+    core::fmt::{self, Display, Formatter},
+    fehler::{throw, throws},
+    std::{
+        string::{String, ToString},
+        sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    },
+};
+
+/// An [`InfiniteChannel`] backed by [`std::sync::mpsc`].
+///
+/// The [`Producer`] of the channel may be cloned to allow multiple [`Producer`]s to store goods into the same channel.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct StdInfiniteChannel;
+
+impl<G> InfiniteChannel<G> for StdInfiniteChannel {
+    type Producer = StdInfiniteProducer<G>;
+    type Consumer = StdInfiniteConsumer<G>;
+
+    fn establish<S>(name_str: &S) -> (Self::Producer, Self::Consumer)
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let name = name_str.as_ref().to_string();
+
+        (
+            StdInfiniteProducer {
+                name: name.clone(),
+                sender,
+            },
+            StdInfiniteConsumer { name, receiver },
+        )
+    }
+}
+
+/// The [`Producer`] of a [`StdInfiniteChannel`].
+#[derive(Debug)]
+pub struct StdInfiniteProducer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The sender of the channel.
+    sender: Sender<G>,
+}
+
+impl<G> Agent for StdInfiniteProducer<G> {
+    type Good = G;
+}
+
+impl<G> Clone for StdInfiniteProducer<G> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<G> Display for StdInfiniteProducer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Producer for StdInfiniteProducer<G> {
+    type Flaws = WithdrawnDemand;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        if let Err(error) = self.sender.send(good) {
+            throw!(self.recall(Fault::Defect(WithdrawnDemand), error.0));
+        }
+    }
+}
+
+/// The [`Consumer`] of a [`StdInfiniteChannel`].
+#[derive(Debug)]
+pub struct StdInfiniteConsumer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The receiver of the channel.
+    receiver: Receiver<G>,
+}
+
+impl<G> Agent for StdInfiniteConsumer<G> {
+    type Good = G;
+}
+
+impl<G> Consumer for StdInfiniteConsumer<G> {
+    type Flaws = ConsumptionFlaws<WithdrawnSupply>;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.receiver.try_recv() {
+            Ok(good) => good,
+            Err(TryRecvError::Empty) => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+            Err(TryRecvError::Disconnected) => throw!(self.failure(Fault::Defect(WithdrawnSupply))),
+        }
+    }
+
+    /// Retrieves the next good from the channel, blocking until one is available.
+    ///
+    /// # Errors
+    ///
+    /// If the channel is empty and all [`Producer`]s have been dropped, `demand` shall throw a [`WithdrawnSupply`].
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good {
+        match self.receiver.recv() {
+            Ok(good) => good,
+            Err(_) => throw!(Failure::new(self, Fault::Defect(WithdrawnSupply))),
+        }
+    }
+}
+
+impl<G> Display for StdInfiniteConsumer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
@@ -0,0 +1,437 @@
+//! Implements the lock-free rings shared by the agents of the lock-free channels.
+//!
+//! This is the only module that uses `unsafe` code: a ring hands a good from one thread to another through a slot that is not guarded by a lock. Each `unsafe` block states why it is sound, and every item exposed to the rest of the crate is safe to use.
+#![allow(unsafe_code)]
+use {
+    alloc::{boxed::Box, sync::Arc, vec::Vec},
+    core::{
+        cell::{Cell, UnsafeCell},
+        hint,
+        marker::PhantomData,
+        mem::MaybeUninit,
+        sync::atomic::{self, AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+/// Creates the [`SpscTail`] and [`SpscHead`] of a ring with `size` slots.
+///
+/// A `size` of 0 creates a rendezvous ring, which has a single slot that is only available while the [`SpscHead`] is demanding.
+pub(super) fn spsc<G>(size: usize) -> (SpscTail<G>, SpscHead<G>) {
+    let ring = Arc::new(SpscRing::new(size));
+
+    (
+        SpscTail {
+            ring: Arc::clone(&ring),
+            unsync: PhantomData,
+        },
+        SpscHead {
+            ring,
+            unsync: PhantomData,
+        },
+    )
+}
+
+/// The circular buffer shared by an [`SpscTail`] and an [`SpscHead`].
+///
+/// `head` and `tail` increase monotonically (wrapping on overflow); a slot is found by taking an index modulo the number of slots. The slots in `head..tail` hold initialized goods.
+#[derive(Debug)]
+struct SpscRing<G> {
+    /// The slots of the ring.
+    slots: Box<[UnsafeCell<MaybeUninit<G>>]>,
+    /// The index of the next good to be retrieved; only written by the [`SpscHead`].
+    head: AtomicUsize,
+    /// The index of the next good to be stored; only written by the [`SpscTail`].
+    tail: AtomicUsize,
+    /// If the [`SpscTail`] has not been dropped.
+    has_tail: AtomicBool,
+    /// If the [`SpscHead`] has not been dropped.
+    has_head: AtomicBool,
+    /// If the ring is a rendezvous ring.
+    is_rendezvous: bool,
+    /// If the [`SpscHead`] is waiting for a good from a rendezvous ring.
+    is_demanding: AtomicBool,
+}
+
+impl<G> SpscRing<G> {
+    /// Creates a new [`SpscRing`] with `size` slots.
+    fn new(size: usize) -> Self {
+        Self {
+            slots: (0..size.max(1))
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            has_tail: AtomicBool::new(true),
+            has_head: AtomicBool::new(true),
+            is_rendezvous: size == 0,
+            is_demanding: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns if `self` holds no goods.
+    fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Relaxed) == self.tail.load(Ordering::Acquire)
+    }
+
+    /// Returns the number of goods `self` is currently able to hold.
+    fn capacity(&self) -> usize {
+        if self.is_rendezvous {
+            usize::from(self.is_demanding.load(Ordering::Acquire))
+        } else {
+            self.slots.len()
+        }
+    }
+
+    /// Returns the slot for `index`.
+    ///
+    /// Only returns [`None`] if `self` has no slots, which [`SpscRing::new()`] prevents.
+    fn slot(&self, index: usize) -> Option<&UnsafeCell<MaybeUninit<G>>> {
+        self.slots.get(index.checked_rem(self.slots.len())?)
+    }
+
+    /// Stores `good` at the tail of `self`, returning `good` if `self` is full.
+    ///
+    /// Only called through the [`SpscTail`], of which there is exactly one.
+    fn push(&self, good: G) -> Result<(), G> {
+        let tail = self.tail.load(Ordering::Relaxed);
+
+        if tail.wrapping_sub(self.head.load(Ordering::Acquire)) >= self.capacity() {
+            return Err(good);
+        }
+
+        let slot = match self.slot(tail) {
+            Some(slot) => slot,
+            None => return Err(good),
+        };
+
+        // SAFETY: Only the single SpscTail writes slots and it cannot be shared between threads, so no other write is in progress. The slot at `tail` is not in `head..tail`, so the SpscHead does not read it, and the Acquire load of `head` ensures the SpscHead has finished reading any previous good in the slot.
+        unsafe {
+            (*slot.get()).as_mut_ptr().write(good);
+        }
+
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Removes and returns the good at the head of `self`.
+    ///
+    /// Only called through the [`SpscHead`], of which there is exactly one, or while dropping `self`.
+    fn pop(&self) -> Option<G> {
+        let head = self.head.load(Ordering::Relaxed);
+
+        if head == self.tail.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let slot = self.slot(head)?;
+        // SAFETY: Only the single SpscHead reads slots and it cannot be shared between threads, so no other read is in progress. The slot at `head` is in `head..tail`, so it holds an initialized good that the SpscTail does not write until `head` is published, and the Acquire load of `tail` ensures the write of the good is visible. Advancing `head` afterwards ensures the good is read only once.
+        let good = unsafe { (*slot.get()).as_ptr().read() };
+
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(good)
+    }
+}
+
+impl<G> Drop for SpscRing<G> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+// SAFETY: The slots are only written by the SpscTail and only read by the SpscHead, neither of which can be shared between threads, and each slot is handed from one to the other by the Release store and Acquire load of `head` and `tail`. Goods move between threads, so `G` must be `Send`.
+unsafe impl<G: Send> Sync for SpscRing<G> {}
+
+/// The end of an [`SpscRing`] that stores goods.
+///
+/// There is exactly one [`SpscTail`] for each ring; it may be sent to another thread but may not be shared between threads.
+#[derive(Debug)]
+pub(super) struct SpscTail<G> {
+    /// The ring.
+    ring: Arc<SpscRing<G>>,
+    /// Prevents implementing [`Sync`] so that only one thread may store at a time.
+    unsync: PhantomData<Cell<()>>,
+}
+
+impl<G> SpscTail<G> {
+    /// Stores `good` into the ring, returning `good` if the ring is full.
+    pub(super) fn push(&self, good: G) -> Result<(), G> {
+        self.ring.push(good)
+    }
+
+    /// Returns if the [`SpscHead`] has not been dropped.
+    pub(super) fn has_head(&self) -> bool {
+        self.ring.has_head.load(Ordering::Acquire)
+    }
+}
+
+impl<G> Drop for SpscTail<G> {
+    fn drop(&mut self) {
+        self.ring.has_tail.store(false, Ordering::Release);
+    }
+}
+
+/// The end of an [`SpscRing`] that retrieves goods.
+///
+/// There is exactly one [`SpscHead`] for each ring; it may be sent to another thread but may not be shared between threads.
+#[derive(Debug)]
+pub(super) struct SpscHead<G> {
+    /// The ring.
+    ring: Arc<SpscRing<G>>,
+    /// Prevents implementing [`Sync`] so that only one thread may retrieve at a time.
+    unsync: PhantomData<Cell<()>>,
+}
+
+impl<G> SpscHead<G> {
+    /// Removes and returns the oldest good in the ring.
+    pub(super) fn pop(&self) -> Option<G> {
+        self.ring.pop()
+    }
+
+    /// Returns if the ring holds no goods.
+    pub(super) fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    /// Returns if the [`SpscTail`] has not been dropped.
+    pub(super) fn has_tail(&self) -> bool {
+        self.ring.has_tail.load(Ordering::Acquire)
+    }
+
+    /// Sets if `self` is waiting for a good, which is the only time a rendezvous ring is able to hold one.
+    pub(super) fn set_demanding(&self, is_demanding: bool) {
+        self.ring
+            .is_demanding
+            .store(is_demanding, Ordering::Release);
+    }
+}
+
+impl<G> Drop for SpscHead<G> {
+    fn drop(&mut self) {
+        self.ring.has_head.store(false, Ordering::Release);
+    }
+}
+
+/// A slot of an [`MpmcRing`].
+#[derive(Debug)]
+struct Slot<G> {
+    /// The position at which the slot may next be accessed.
+    ///
+    /// If `stamp` equals a position at which a good is stored, the slot is vacant; if `stamp` is one more than a position at which a good is retrieved, the slot holds a good.
+    stamp: AtomicUsize,
+    /// The good held by the slot.
+    good: UnsafeCell<MaybeUninit<G>>,
+}
+
+/// An array queue that may be shared by any number of threads that store and retrieve goods, as described by Dmitry Vyukov.
+///
+/// A position combines the index of a slot in its lower bits with the number of laps around the ring in its upper bits.
+#[derive(Debug)]
+pub(super) struct MpmcRing<G> {
+    /// The slots of the ring.
+    slots: Box<[Slot<G>]>,
+    /// The value of one lap; the lowest power of two greater than the number of slots.
+    one_lap: usize,
+    /// The position of the next good to be retrieved.
+    head: AtomicUsize,
+    /// The position of the next good to be stored.
+    tail: AtomicUsize,
+    /// If the ring is a rendezvous ring.
+    is_rendezvous: bool,
+    /// The number of threads waiting for a good from a rendezvous ring for which no good has been stored.
+    ///
+    /// A thread that stores a good claims a waiting thread by decrementing the count before storing, so that each stored good has a thread waiting for it.
+    demand_count: AtomicUsize,
+}
+
+impl<G> MpmcRing<G> {
+    /// The largest number of slots in an [`MpmcRing`], so that the value of one lap is representable.
+    const MAX_SIZE: usize = usize::MAX / 2;
+
+    /// Creates a new [`MpmcRing`] with `size` slots.
+    ///
+    /// A `size` of 0 creates a rendezvous ring, which has a single slot that is only available while a thread is demanding. A `size` greater than [`MpmcRing::MAX_SIZE`] is reduced to it.
+    pub(super) fn new(size: usize) -> Self {
+        let len = size.clamp(1, Self::MAX_SIZE);
+
+        Self {
+            slots: (0..len)
+                .map(|index| Slot {
+                    stamp: AtomicUsize::new(index),
+                    good: UnsafeCell::new(MaybeUninit::uninit()),
+                })
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+            // len is at most usize::MAX / 2, so the next power of two is representable.
+            one_lap: len.saturating_add(1).next_power_of_two(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            is_rendezvous: size == 0,
+            demand_count: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the position that follows `position`.
+    fn advance(&self, position: usize) -> usize {
+        if self.index(position).wrapping_add(1) < self.slots.len() {
+            position.wrapping_add(1)
+        } else {
+            (position & !self.one_lap.wrapping_sub(1)).wrapping_add(self.one_lap)
+        }
+    }
+
+    /// Returns the index of the slot at `position`.
+    const fn index(&self, position: usize) -> usize {
+        position & self.one_lap.wrapping_sub(1)
+    }
+
+    /// Returns the slot at `position`.
+    ///
+    /// Only returns [`None`] if `position` was not created by [`MpmcRing::advance()`].
+    fn slot(&self, position: usize) -> Option<&Slot<G>> {
+        self.slots.get(self.index(position))
+    }
+
+    /// Stores `good` at the tail of `self`, returning `good` if `self` is full.
+    ///
+    /// A rendezvous ring is full unless a waiting thread is able to be claimed.
+    pub(super) fn push(&self, good: G) -> Result<(), G> {
+        if !self.is_rendezvous {
+            return self.store(good);
+        }
+
+        if self
+            .demand_count
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                count.checked_sub(1)
+            })
+            .is_err()
+        {
+            return Err(good);
+        }
+
+        let outcome = self.store(good);
+
+        if outcome.is_err() {
+            // Release the claim on the waiting thread since no good was stored for it.
+            self.add_demand();
+        }
+
+        outcome
+    }
+
+    /// Increments the number of waiting threads of a rendezvous ring.
+    pub(super) fn add_demand(&self) {
+        if self.is_rendezvous {
+            let _ = self.demand_count.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+
+    /// Decrements the number of waiting threads of a rendezvous ring.
+    pub(super) fn remove_demand(&self) {
+        if self.is_rendezvous {
+            let _ = self.demand_count.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+
+    /// Stores `good` in the slot at the tail of `self`, returning `good` if `self` is full.
+    fn store(&self, good: G) -> Result<(), G> {
+        let mut tail = self.tail.load(Ordering::Relaxed);
+
+        loop {
+            let slot = match self.slot(tail) {
+                Some(slot) => slot,
+                None => return Err(good),
+            };
+            let stamp = slot.stamp.load(Ordering::Acquire);
+
+            if tail == stamp {
+                match self.tail.compare_exchange_weak(
+                    tail,
+                    self.advance(tail),
+                    Ordering::SeqCst,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: The stamp of the slot equals `tail`, so the slot is vacant, and the successful compare-exchange of `tail` makes this the only thread that claimed it. No other thread reads or writes the slot until its stamp is published below, and the Acquire load of the stamp ensures the previous good in the slot has been read.
+                        unsafe {
+                            (*slot.good.get()).as_mut_ptr().write(good);
+                        }
+
+                        slot.stamp.store(tail.wrapping_add(1), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => {
+                        tail = current;
+                    }
+                }
+            } else if stamp.wrapping_add(self.one_lap) == tail.wrapping_add(1) {
+                // The slot holds a good from the previous lap; check if the ring is full.
+                atomic::fence(Ordering::SeqCst);
+
+                if self.head.load(Ordering::Relaxed).wrapping_add(self.one_lap) == tail {
+                    return Err(good);
+                }
+
+                tail = self.tail.load(Ordering::Relaxed);
+            } else {
+                // Another thread has claimed `tail` but not yet published its stamp.
+                hint::spin_loop();
+                tail = self.tail.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Removes and returns the good at the head of `self`.
+    pub(super) fn pop(&self) -> Option<G> {
+        let mut head = self.head.load(Ordering::Relaxed);
+
+        loop {
+            let slot = self.slot(head)?;
+            let stamp = slot.stamp.load(Ordering::Acquire);
+
+            if head.wrapping_add(1) == stamp {
+                match self.head.compare_exchange_weak(
+                    head,
+                    self.advance(head),
+                    Ordering::SeqCst,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: The stamp of the slot is one more than `head`, so the slot holds a good whose write is visible due to the Acquire load of the stamp, and the successful compare-exchange of `head` makes this the only thread that claimed it. No other thread reads or writes the slot until its stamp is published below, so the good is read only once.
+                        let good = unsafe { (*slot.good.get()).as_ptr().read() };
+
+                        slot.stamp
+                            .store(head.wrapping_add(self.one_lap), Ordering::Release);
+                        return Some(good);
+                    }
+                    Err(current) => {
+                        head = current;
+                    }
+                }
+            } else if stamp == head {
+                // The slot is vacant; check if the ring is empty.
+                atomic::fence(Ordering::SeqCst);
+
+                if self.tail.load(Ordering::Relaxed) == head {
+                    return None;
+                }
+
+                head = self.head.load(Ordering::Relaxed);
+            } else {
+                // Another thread has claimed `head` but not yet published its stamp.
+                hint::spin_loop();
+                head = self.head.load(Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+impl<G> Drop for MpmcRing<G> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+// SAFETY: The good in each slot is only accessed by the one thread that claimed the slot, and each slot is handed between threads by the Release store and Acquire load of its stamp. Goods move between threads, so `G` must be `Send`.
+unsafe impl<G: Send> Sync for MpmcRing<G> {}
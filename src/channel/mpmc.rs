@@ -0,0 +1,206 @@
+//! Implements a lock-free [`FiniteChannel`] with any number of [`Producer`]s and [`Consumer`]s.
+use {
+    super::{lockfree::MpmcRing, FiniteChannel, WithdrawnDemand, WithdrawnSupply},
+    crate::{
+        select::backoff, Agent, Consumer, ConsumptionFlaws, EmptyStock, Failure, Fault, Flaws,
+        FullStock, Producer, ProductionFlaws, Recall,
+    },
+    alloc::{
+        string::{String, ToString},
+        sync::Arc,
+    },
+    core::{
+        fmt::{self, Display, Formatter},
+        sync::atomic::{AtomicUsize, Ordering},
+    },
+    fehler::{throw, throws},
+};
+
+/// A [`FiniteChannel`] that connects any number of [`Producer`]s to any number of [`Consumer`]s using only atomic operations.
+///
+/// Both the [`Producer`] and [`Consumer`] may be cloned and shared between threads. All [`Consumer`]s retrieve from the same stock, so each good is retrieved by exactly one [`Consumer`]. A [`WithdrawnDemand`] or [`WithdrawnSupply`] is only thrown once the last clone of the opposite agent has been dropped.
+///
+/// The stock of the channel holds at most `usize::MAX / 2` goods; a larger `size` is reduced to that, which could not be allocated anyway.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct MpmcChannel;
+
+impl<G> FiniteChannel<G> for MpmcChannel {
+    type Producer = MpmcProducer<G>;
+    type Consumer = MpmcConsumer<G>;
+
+    fn establish<S>(name_str: &S, size: usize) -> (Self::Producer, Self::Consumer)
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let name = name_str.as_ref().to_string();
+        let shared = Arc::new(Shared {
+            ring: MpmcRing::new(size),
+            producer_count: AtomicUsize::new(1),
+            consumer_count: AtomicUsize::new(1),
+        });
+
+        (
+            MpmcProducer {
+                name: name.clone(),
+                shared: Arc::clone(&shared),
+            },
+            MpmcConsumer { name, shared },
+        )
+    }
+}
+
+/// The state shared by the agents of an [`MpmcChannel`].
+#[derive(Debug)]
+struct Shared<G> {
+    /// The ring of the channel.
+    ring: MpmcRing<G>,
+    /// The number of [`Producer`]s connected to the channel.
+    producer_count: AtomicUsize,
+    /// The number of [`Consumer`]s connected to the channel.
+    consumer_count: AtomicUsize,
+}
+
+/// The [`Producer`] of an [`MpmcChannel`].
+#[derive(Debug)]
+pub struct MpmcProducer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The state shared with the other agents of the channel.
+    shared: Arc<Shared<G>>,
+}
+
+impl<G> Agent for MpmcProducer<G> {
+    type Good = G;
+}
+
+impl<G> Clone for MpmcProducer<G> {
+    fn clone(&self) -> Self {
+        let _ = self.shared.producer_count.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            name: self.name.clone(),
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<G> Display for MpmcProducer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Drop for MpmcProducer<G> {
+    fn drop(&mut self) {
+        let _ = self.shared.producer_count.fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl<G> Producer for MpmcProducer<G> {
+    type Flaws = ProductionFlaws<WithdrawnDemand>;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        if self.shared.consumer_count.load(Ordering::Acquire) == 0 {
+            throw!(self.recall(Fault::Defect(WithdrawnDemand), good));
+        }
+
+        if let Err(good) = self.shared.ring.push(good) {
+            throw!(self.recall(Fault::Insufficiency(FullStock), good));
+        }
+    }
+}
+
+/// The [`Consumer`] of an [`MpmcChannel`].
+#[derive(Debug)]
+pub struct MpmcConsumer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The state shared with the other agents of the channel.
+    shared: Arc<Shared<G>>,
+}
+
+impl<G> Agent for MpmcConsumer<G> {
+    type Good = G;
+}
+
+impl<G> Clone for MpmcConsumer<G> {
+    fn clone(&self) -> Self {
+        let _ = self.shared.consumer_count.fetch_add(1, Ordering::Relaxed);
+
+        Self {
+            name: self.name.clone(),
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<G> Consumer for MpmcConsumer<G> {
+    type Flaws = ConsumptionFlaws<WithdrawnSupply>;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        let good = match self.shared.ring.pop() {
+            Some(good) => good,
+            None if self.shared.producer_count.load(Ordering::Acquire) != 0 => {
+                throw!(self.failure(Fault::Insufficiency(EmptyStock)))
+            }
+            // The last Producer may have stored a good before it was dropped.
+            None => self
+                .shared
+                .ring
+                .pop()
+                .ok_or_else(|| self.failure(Fault::Defect(WithdrawnSupply)))?,
+        };
+
+        // A good in a rendezvous ring was stored for a Consumer waiting in demand, which continues to wait.
+        self.shared.ring.add_demand();
+        good
+    }
+
+    /// Retrieves the next good from the channel, polling until one is available.
+    ///
+    /// Between polls, the current thread yields if `std` is available; otherwise the processor is signalled that it is spinning.
+    ///
+    /// # Errors
+    ///
+    /// If the channel is empty and all [`Producer`]s have been dropped, `demand` shall throw a [`WithdrawnSupply`].
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good {
+        self.shared.ring.add_demand();
+
+        loop {
+            if let Some(good) = self.shared.ring.pop() {
+                // The Producer that stored the good claimed the demand.
+                break good;
+            }
+
+            if self.shared.producer_count.load(Ordering::Acquire) == 0 {
+                // The last Producer may have stored a good before it was dropped.
+                if let Some(good) = self.shared.ring.pop() {
+                    break good;
+                }
+
+                self.shared.ring.remove_demand();
+                throw!(Failure::new(self, Fault::Defect(WithdrawnSupply)));
+            }
+
+            backoff();
+        }
+    }
+}
+
+impl<G> Display for MpmcConsumer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Drop for MpmcConsumer<G> {
+    fn drop(&mut self) {
+        let _ = self.shared.consumer_count.fetch_sub(1, Ordering::Release);
+    }
+}
@@ -0,0 +1,252 @@
+//! Implements a channel that exchanges a single good.
+use {
+    super::{WithdrawnDemand, WithdrawnSupply},
+    crate::{
+        sync::lock, Agent, Consumer, ConsumptionFlaws, EmptyStock, Failure, Fault, Flawless, Flaws,
+        Producer, Recall,
+    },
+    core::fmt::{self, Display, Formatter},
+    fehler::{throw, throws},
+    std::{
+        string::{String, ToString},
+        sync::{Arc, Condvar, Mutex, PoisonError},
+    },
+};
+
+/// A channel that exchanges exactly one good between a [`Producer`] and a [`Consumer`].
+///
+/// Once the [`Producer`] has stored a good, its capacity is spent and any further production throws [`Spent`]. Once the [`Consumer`] has retrieved the good, any further consumption throws [`WithdrawnSupply`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct OneshotChannel;
+
+impl OneshotChannel {
+    /// Creates the [`Producer`] and [`Consumer`] connected to a oneshot channel.
+    pub fn establish<G, S>(name_str: &S) -> (OneshotProducer<G>, OneshotConsumer<G>)
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let name = name_str.as_ref().to_string();
+        let market = Arc::new(Market {
+            stock: Mutex::new(Stock {
+                good: None,
+                is_spent: false,
+                has_producer: true,
+                has_consumer: true,
+            }),
+            has_good: Condvar::new(),
+        });
+
+        (
+            OneshotProducer {
+                name: name.clone(),
+                market: Arc::clone(&market),
+            },
+            OneshotConsumer { name, market },
+        )
+    }
+}
+
+/// The defect thrown when a [`Producer`] attempts to produce to a [`OneshotChannel`] that has already been given its good.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Spent;
+
+impl Display for Spent {
+    /// Writes "capacity has been spent".
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "capacity has been spent")
+    }
+}
+
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+impl std::error::Error for Spent {}
+
+impl Flaws for Spent {
+    type Insufficiency = Flawless;
+    type Defect = Self;
+}
+
+/// The defect thrown when the [`Producer`] of a [`OneshotChannel`] fails to store a good.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum OneshotDefect {
+    /// The [`Producer`] has already stored its good.
+    Spent(Spent),
+    /// The [`Consumer`] has been dropped.
+    WithdrawnDemand(WithdrawnDemand),
+}
+
+impl Display for OneshotDefect {
+    /// Writes the display of the defect.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Spent(ref spent) => write!(f, "{}", spent),
+            Self::WithdrawnDemand(ref withdrawn_demand) => write!(f, "{}", withdrawn_demand),
+        }
+    }
+}
+
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+impl std::error::Error for OneshotDefect {}
+
+impl Flaws for OneshotDefect {
+    type Insufficiency = Flawless;
+    type Defect = Self;
+}
+
+impl From<Spent> for OneshotDefect {
+    fn from(spent: Spent) -> Self {
+        Self::Spent(spent)
+    }
+}
+
+impl From<WithdrawnDemand> for OneshotDefect {
+    fn from(withdrawn_demand: WithdrawnDemand) -> Self {
+        Self::WithdrawnDemand(withdrawn_demand)
+    }
+}
+
+/// The state shared by the agents of a [`OneshotChannel`].
+#[derive(Debug)]
+struct Market<G> {
+    /// The stock of the channel.
+    stock: Mutex<Stock<G>>,
+    /// Notified when the good is stored or the [`Producer`] is dropped.
+    has_good: Condvar,
+}
+
+/// The good stored in a [`OneshotChannel`] along with the state of its agents.
+#[derive(Debug)]
+struct Stock<G> {
+    /// The good that has been stored but not retrieved.
+    good: Option<G>,
+    /// If the [`Producer`] has stored its good.
+    is_spent: bool,
+    /// If the [`Producer`] is connected to the channel.
+    has_producer: bool,
+    /// If the [`Consumer`] is connected to the channel.
+    has_consumer: bool,
+}
+
+impl<G> Stock<G> {
+    /// Returns if the good will never be available to retrieve.
+    const fn is_withdrawn(&self) -> bool {
+        self.good.is_none() && (self.is_spent || !self.has_producer)
+    }
+}
+
+/// The [`Producer`] of a [`OneshotChannel`].
+#[derive(Debug)]
+pub struct OneshotProducer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The market of the channel.
+    market: Arc<Market<G>>,
+}
+
+impl<G> Agent for OneshotProducer<G> {
+    type Good = G;
+}
+
+impl<G> Display for OneshotProducer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Drop for OneshotProducer<G> {
+    fn drop(&mut self) {
+        lock(&self.market.stock).has_producer = false;
+        self.market.has_good.notify_all();
+    }
+}
+
+impl<G> Producer for OneshotProducer<G> {
+    type Flaws = OneshotDefect;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        let mut stock = lock(&self.market.stock);
+
+        if stock.is_spent {
+            throw!(self.recall(Fault::Defect(Spent.into()), good));
+        }
+
+        if !stock.has_consumer {
+            throw!(self.recall(Fault::Defect(WithdrawnDemand.into()), good));
+        }
+
+        stock.good = Some(good);
+        stock.is_spent = true;
+        self.market.has_good.notify_all();
+    }
+}
+
+/// The [`Consumer`] of a [`OneshotChannel`].
+#[derive(Debug)]
+pub struct OneshotConsumer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The market of the channel.
+    market: Arc<Market<G>>,
+}
+
+impl<G> Agent for OneshotConsumer<G> {
+    type Good = G;
+}
+
+impl<G> Consumer for OneshotConsumer<G> {
+    type Flaws = ConsumptionFlaws<WithdrawnSupply>;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        let mut stock = lock(&self.market.stock);
+
+        match stock.good.take() {
+            Some(good) => good,
+            None if stock.is_withdrawn() => throw!(self.failure(Fault::Defect(WithdrawnSupply))),
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+
+    /// Retrieves the good from the channel, blocking until it is available.
+    ///
+    /// # Errors
+    ///
+    /// If the good has already been retrieved or the [`Producer`] was dropped without storing a good, `demand` shall throw a [`WithdrawnSupply`].
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good {
+        let mut stock = lock(&self.market.stock);
+
+        loop {
+            if let Some(good) = stock.good.take() {
+                break good;
+            }
+
+            if stock.is_withdrawn() {
+                throw!(Failure::new(self, Fault::Defect(WithdrawnSupply)));
+            }
+
+            stock = self
+                .market
+                .has_good
+                .wait(stock)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+impl<G> Display for OneshotConsumer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Drop for OneshotConsumer<G> {
+    fn drop(&mut self) {
+        lock(&self.market.stock).has_consumer = false;
+    }
+}
@@ -0,0 +1,148 @@
+//! Implements a lock-free [`FiniteChannel`] with a single [`Producer`] and a single [`Consumer`].
+use {
+    super::{
+        lockfree::{self, SpscHead, SpscTail},
+        FiniteChannel, WithdrawnDemand, WithdrawnSupply,
+    },
+    crate::{
+        select::backoff, Agent, Consumer, ConsumptionFlaws, EmptyStock, Failure, Fault, Flaws,
+        FullStock, Producer, ProductionFlaws, Recall,
+    },
+    alloc::string::{String, ToString},
+    core::fmt::{self, Display, Formatter},
+    fehler::{throw, throws},
+};
+
+/// A [`FiniteChannel`] that connects a single [`Producer`] to a single [`Consumer`] using only atomic operations.
+///
+/// Neither agent ever waits on a lock, so producing and consuming are wait-free.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct SpscChannel;
+
+impl<G> FiniteChannel<G> for SpscChannel {
+    type Producer = SpscProducer<G>;
+    type Consumer = SpscConsumer<G>;
+
+    fn establish<S>(name_str: &S, size: usize) -> (Self::Producer, Self::Consumer)
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        let name = name_str.as_ref().to_string();
+        let (tail, head) = lockfree::spsc(size);
+
+        (
+            SpscProducer {
+                name: name.clone(),
+                tail,
+            },
+            SpscConsumer { name, head },
+        )
+    }
+}
+
+/// The [`Producer`] of an [`SpscChannel`].
+///
+/// An [`SpscProducer`] may be sent to another thread but may not be shared between threads.
+#[derive(Debug)]
+pub struct SpscProducer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The end of the ring that stores goods.
+    tail: SpscTail<G>,
+}
+
+impl<G> Agent for SpscProducer<G> {
+    type Good = G;
+}
+
+impl<G> Display for SpscProducer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Producer for SpscProducer<G> {
+    type Flaws = ProductionFlaws<WithdrawnDemand>;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        if !self.tail.has_head() {
+            throw!(self.recall(Fault::Defect(WithdrawnDemand), good));
+        }
+
+        if let Err(good) = self.tail.push(good) {
+            throw!(self.recall(Fault::Insufficiency(FullStock), good));
+        }
+    }
+}
+
+/// The [`Consumer`] of an [`SpscChannel`].
+///
+/// An [`SpscConsumer`] may be sent to another thread but may not be shared between threads.
+#[derive(Debug)]
+pub struct SpscConsumer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The end of the ring that retrieves goods.
+    head: SpscHead<G>,
+}
+
+impl<G> Agent for SpscConsumer<G> {
+    type Good = G;
+}
+
+impl<G> Consumer for SpscConsumer<G> {
+    type Flaws = ConsumptionFlaws<WithdrawnSupply>;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        if let Some(good) = self.head.pop() {
+            good
+        } else if self.head.has_tail() {
+            throw!(self.failure(Fault::Insufficiency(EmptyStock)));
+        } else {
+            // The Producer may have stored a good before it was dropped.
+            match self.head.pop() {
+                Some(good) => good,
+                None => throw!(self.failure(Fault::Defect(WithdrawnSupply))),
+            }
+        }
+    }
+
+    /// Retrieves the next good from the channel, polling until one is available.
+    ///
+    /// Between polls, the current thread yields if `std` is available; otherwise the processor is signalled that it is spinning.
+    ///
+    /// # Errors
+    ///
+    /// If the channel is empty and the [`Producer`] has been dropped, `demand` shall throw a [`WithdrawnSupply`].
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good {
+        loop {
+            self.head.set_demanding(true);
+
+            if !self.head.is_empty() {
+                // Stop accepting a handoff before retrieving so that a good is only stored while the Consumer is waiting.
+                self.head.set_demanding(false);
+
+                if let Some(good) = self.head.pop() {
+                    break good;
+                }
+            } else if !self.head.has_tail() && self.head.is_empty() {
+                self.head.set_demanding(false);
+                throw!(Failure::new(self, Fault::Defect(WithdrawnSupply)));
+            }
+
+            backoff();
+        }
+    }
+}
+
+impl<G> Display for SpscConsumer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
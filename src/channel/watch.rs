@@ -0,0 +1,250 @@
+//! Implements a channel that only retains the most recent good.
+use {
+    super::{WithdrawnDemand, WithdrawnSupply},
+    crate::{
+        sync::lock, Agent, Consumer, ConsumptionFlaws, EmptyStock, Failure, Fault, Flaws, Producer,
+        Recall,
+    },
+    core::{
+        cell::Cell,
+        fmt::{self, Display, Formatter},
+    },
+    fehler::{throw, throws},
+    std::{
+        string::{String, ToString},
+        sync::{Arc, Condvar, Mutex, PoisonError},
+    },
+};
+
+/// A channel that retains only the most recent good.
+///
+/// Storing a good replaces the retained good, so a [`Producer`] never throws [`FullStock`](crate::FullStock). Each [`Consumer`] retrieves a clone of the retained good only if it has been replaced since that [`Consumer`] last retrieved a good; goods that are replaced before being retrieved are never seen by that [`Consumer`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct WatchChannel;
+
+impl WatchChannel {
+    /// Creates the [`Producer`] and [`Consumer`] connected to a watch channel.
+    pub fn establish<G, S>(name_str: &S) -> (WatchProducer<G>, WatchConsumer<G>)
+    where
+        G: Clone,
+        S: AsRef<str> + ?Sized,
+    {
+        let name = name_str.as_ref().to_string();
+        let market = Arc::new(Market {
+            stock: Mutex::new(Stock {
+                good: None,
+                version: 0,
+                producer_count: 1,
+                consumer_count: 1,
+            }),
+            has_change: Condvar::new(),
+        });
+
+        (
+            WatchProducer {
+                name: name.clone(),
+                market: Arc::clone(&market),
+            },
+            WatchConsumer {
+                name,
+                market,
+                version: Cell::new(0),
+            },
+        )
+    }
+}
+
+/// The state shared by the agents of a [`WatchChannel`].
+#[derive(Debug)]
+struct Market<G> {
+    /// The stock of the channel.
+    stock: Mutex<Stock<G>>,
+    /// Notified when a good is stored or a [`Producer`] is dropped.
+    has_change: Condvar,
+}
+
+/// The good retained by a [`WatchChannel`] along with the state of its agents.
+#[derive(Debug)]
+struct Stock<G> {
+    /// The most recently stored good.
+    good: Option<G>,
+    /// The number of goods that have been stored.
+    version: u64,
+    /// The number of [`Producer`]s connected to the channel.
+    producer_count: usize,
+    /// The number of [`Consumer`]s connected to the channel.
+    consumer_count: usize,
+}
+
+/// The [`Producer`] of a [`WatchChannel`].
+#[derive(Debug)]
+pub struct WatchProducer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The market of the channel.
+    market: Arc<Market<G>>,
+}
+
+impl<G> WatchProducer<G> {
+    /// Creates a new [`Consumer`] that has not retrieved any good.
+    #[must_use]
+    pub fn subscribe(&self) -> WatchConsumer<G> {
+        let mut stock = lock(&self.market.stock);
+
+        stock.consumer_count = stock.consumer_count.saturating_add(1);
+
+        WatchConsumer {
+            name: self.name.clone(),
+            market: Arc::clone(&self.market),
+            version: Cell::new(0),
+        }
+    }
+}
+
+impl<G> Agent for WatchProducer<G> {
+    type Good = G;
+}
+
+impl<G> Clone for WatchProducer<G> {
+    fn clone(&self) -> Self {
+        let mut stock = lock(&self.market.stock);
+
+        stock.producer_count = stock.producer_count.saturating_add(1);
+
+        Self {
+            name: self.name.clone(),
+            market: Arc::clone(&self.market),
+        }
+    }
+}
+
+impl<G> Display for WatchProducer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Drop for WatchProducer<G> {
+    fn drop(&mut self) {
+        let mut stock = lock(&self.market.stock);
+
+        stock.producer_count = stock.producer_count.saturating_sub(1);
+        self.market.has_change.notify_all();
+    }
+}
+
+impl<G> Producer for WatchProducer<G> {
+    type Flaws = WithdrawnDemand;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        let mut stock = lock(&self.market.stock);
+
+        if stock.consumer_count == 0 {
+            throw!(self.recall(Fault::Defect(WithdrawnDemand), good));
+        }
+
+        stock.good = Some(good);
+        stock.version = stock.version.wrapping_add(1);
+        self.market.has_change.notify_all();
+    }
+}
+
+/// The [`Consumer`] of a [`WatchChannel`].
+///
+/// Each [`WatchConsumer`] tracks the last good it retrieved; a clone has retrieved the same good as the original.
+#[derive(Debug)]
+pub struct WatchConsumer<G> {
+    /// The name of the channel.
+    name: String,
+    /// The market of the channel.
+    market: Arc<Market<G>>,
+    /// The version of the last good retrieved by `self`.
+    version: Cell<u64>,
+}
+
+impl<G: Clone> WatchConsumer<G> {
+    /// Retrieves the retained good from `stock` if it has changed, returning [`None`] if it has not changed and the channel has a [`Producer`].
+    fn take(&self, stock: &Stock<G>) -> Option<Result<G, WithdrawnSupply>> {
+        match stock.good {
+            Some(ref good) if stock.version != self.version.get() => {
+                self.version.set(stock.version);
+                Some(Ok(good.clone()))
+            }
+            _ if stock.producer_count == 0 => Some(Err(WithdrawnSupply)),
+            _ => None,
+        }
+    }
+}
+
+impl<G> Agent for WatchConsumer<G> {
+    type Good = G;
+}
+
+impl<G> Clone for WatchConsumer<G> {
+    fn clone(&self) -> Self {
+        let mut stock = lock(&self.market.stock);
+
+        stock.consumer_count = stock.consumer_count.saturating_add(1);
+
+        Self {
+            name: self.name.clone(),
+            market: Arc::clone(&self.market),
+            version: self.version.clone(),
+        }
+    }
+}
+
+impl<G: Clone> Consumer for WatchConsumer<G> {
+    type Flaws = ConsumptionFlaws<WithdrawnSupply>;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.take(&lock(&self.market.stock)) {
+            Some(Ok(good)) => good,
+            Some(Err(defect)) => throw!(self.failure(Fault::Defect(defect))),
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+
+    /// Retrieves the retained good from the channel, blocking until it has changed.
+    ///
+    /// # Errors
+    ///
+    /// If the retained good has not changed and all [`Producer`]s have been dropped, `demand` shall throw a [`WithdrawnSupply`].
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good {
+        let mut stock = lock(&self.market.stock);
+
+        loop {
+            match self.take(&stock) {
+                Some(Ok(good)) => break good,
+                Some(Err(defect)) => throw!(Failure::new(self, Fault::Defect(defect))),
+                None => {
+                    stock = self
+                        .market
+                        .has_change
+                        .wait(stock)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+            }
+        }
+    }
+}
+
+impl<G> Display for WatchConsumer<G> {
+    /// Writes the name of the channel.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> Drop for WatchConsumer<G> {
+    fn drop(&mut self) {
+        let mut stock = lock(&self.market.stock);
+
+        stock.consumer_count = stock.consumer_count.saturating_sub(1);
+    }
+}
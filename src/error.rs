@@ -2,8 +2,9 @@
 #[cfg(doc)]
 use crate::{Agent, Consumer, Producer};
 
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::ToString};
 use {
-    alloc::string::{String, ToString},
     core::{
         convert::TryFrom,
         fmt::{self, Debug, Display, Formatter},
@@ -64,7 +65,7 @@ where
     F: Flaws,
 {
     /// Returns if `self` is a defect.
-    fn is_defect(&self) -> bool {
+    pub(crate) fn is_defect(&self) -> bool {
         matches!(*self, Self::Defect(_))
     }
 
@@ -79,6 +80,19 @@ where
             Self::Defect(defect) => Fault::Defect(m(defect)),
         }
     }
+
+    /// If `self` is an insufficiency, converts the insufficiency into `W::Insufficiency`; otherwise returns `self`.
+    #[cfg(feature = "std")]
+    fn map_insufficiency<M, W>(self, mut m: M) -> Fault<W>
+    where
+        M: FnMut(F::Insufficiency) -> W::Insufficiency,
+        W: Flaws<Defect = F::Defect>,
+    {
+        match self {
+            Self::Insufficiency(insufficiency) => Fault::Insufficiency(m(insufficiency)),
+            Self::Defect(defect) => Fault::Defect(defect),
+        }
+    }
 }
 
 impl<F, W> Blame<Fault<W>> for Fault<F>
@@ -199,10 +213,17 @@ where
     }
 }
 
+/// The description of an [`Agent`] held by a [`Failure`].
+#[cfg(feature = "alloc")]
+type Description = Cow<'static, str>;
+/// The description of an [`Agent`] held by a [`Failure`].
+#[cfg(not(feature = "alloc"))]
+type Description = &'static str;
+
 /// The error thrown when the action of an [`Agent`] fails.
 pub struct Failure<F: Flaws> {
     /// The description of the [`Agent`].
-    agent_description: String,
+    agent_description: Description,
     /// The cause of the failure.
     fault: Fault<F>,
 }
@@ -212,12 +233,24 @@ where
     F: Flaws,
 {
     /// Creates a new [`Failure`] with the description of `agent`and `fault` that caused the failure.
+    #[cfg(feature = "alloc")]
     pub(crate) fn new<A>(agent: &A, fault: Fault<F>) -> Self
     where
         A: Display,
     {
         Self {
-            agent_description: agent.to_string(),
+            agent_description: Cow::Owned(agent.to_string()),
+            fault,
+        }
+    }
+
+    /// Creates a new [`Failure`] described by `name` without allocating.
+    pub(crate) fn with_name(name: &'static str, fault: Fault<F>) -> Self {
+        Self {
+            #[cfg(feature = "alloc")]
+            agent_description: Cow::Borrowed(name),
+            #[cfg(not(feature = "alloc"))]
+            agent_description: name,
             fault,
         }
     }
@@ -227,6 +260,17 @@ where
         self.fault.is_defect()
     }
 
+    /// Returns a reference to the [`Fault`] that caused `self`.
+    pub fn fault(&self) -> &Fault<F> {
+        &self.fault
+    }
+
+    /// Converts `self` into the [`Fault`] that caused it.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn into_fault(self) -> Fault<F> {
+        self.fault
+    }
+
     /// If `self` is a defect, converts the defect into `W::Defect`; otherwise returns `self`.
     pub fn map_defect<M, W>(self, m: M) -> Failure<W>
     where
@@ -238,6 +282,19 @@ where
             fault: self.fault.map_defect(m),
         }
     }
+
+    /// If `self` is an insufficiency, converts the insufficiency into `W::Insufficiency`; otherwise returns `self`.
+    #[cfg(feature = "std")]
+    pub(crate) fn map_insufficiency<M, W>(self, m: M) -> Failure<W>
+    where
+        M: FnMut(F::Insufficiency) -> W::Insufficiency,
+        W: Flaws<Defect = F::Defect>,
+    {
+        Failure {
+            agent_description: self.agent_description,
+            fault: self.fault.map_insufficiency(m),
+        }
+    }
 }
 
 impl<F, W> Blame<Failure<W>> for Failure<F>
@@ -334,6 +391,28 @@ impl<F: Flaws, G> Recall<F, G> {
     pub(crate) fn new(failure: Failure<F>, good: G) -> Self {
         Self { good, failure }
     }
+
+    /// Returns a reference to the [`Failure`] that caused `self`.
+    pub fn failure(&self) -> &Failure<F> {
+        &self.failure
+    }
+
+    /// Converts `self` into the good that was not produced.
+    pub fn into_good(self) -> G {
+        self.good
+    }
+
+    /// Converts `self` into its [`Failure`], dropping the good.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn into_failure(self) -> Failure<F> {
+        self.failure
+    }
+
+    /// Converts `self` into its [`Failure`] and the good that was not produced.
+    #[cfg(feature = "std")]
+    pub(crate) fn into_parts(self) -> (Failure<F>, G) {
+        (self.failure, self.good)
+    }
 }
 
 impl<F: Flaws, G, W: Flaws, T> Blame<Recall<W, T>> for Recall<F, G>
@@ -567,7 +646,7 @@ where
     /// The error that caused the failure.
     error: FaultConversionError<F, W>,
     /// The name of the [`Agent`] that experienced the failure.
-    agent_description: String,
+    agent_description: Description,
 }
 
 impl<F: Flaws, W: Flaws> Debug for FailureConversionError<F, W>
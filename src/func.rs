@@ -0,0 +1,135 @@
+//! Implements agents that act upon a market via closures.
+use {
+    crate::{Agent, Consumer, Failure, Fault, Flaws, Producer, Recall},
+    alloc::string::{String, ToString},
+    core::{
+        fmt::{self, Debug, Display, Formatter},
+        marker::PhantomData,
+    },
+    fehler::{throw, throws},
+};
+
+/// Creates a [`Producer`] with `name` that stores each good by calling `produce`.
+///
+/// If `produce` fails to store a good, it returns the good with the [`Fault`] so it can be recalled.
+pub fn producer_fn<S, G, F, P>(name_str: &S, produce: P) -> FnProducer<P, G>
+where
+    S: AsRef<str> + ?Sized,
+    F: Flaws,
+    P: Fn(G) -> Result<(), (Fault<F>, G)>,
+{
+    FnProducer {
+        name: name_str.as_ref().to_string(),
+        produce,
+        good: PhantomData,
+    }
+}
+
+/// Creates a [`Consumer`] with `name` that retrieves each good by calling `consume`.
+pub fn consumer_fn<S, G, F, C>(name_str: &S, consume: C) -> FnConsumer<C>
+where
+    S: AsRef<str> + ?Sized,
+    F: Flaws,
+    C: Fn() -> Result<G, Fault<F>>,
+{
+    FnConsumer {
+        name: name_str.as_ref().to_string(),
+        consume,
+    }
+}
+
+/// A [`Producer`] that stores goods by calling a closure.
+///
+/// Created by [`producer_fn()`].
+pub struct FnProducer<P, G> {
+    /// The name of the producer.
+    name: String,
+    /// Stores a good.
+    produce: P,
+    /// The type of the goods.
+    good: PhantomData<fn(G)>,
+}
+
+impl<P, G, F> Agent for FnProducer<P, G>
+where
+    F: Flaws,
+    P: Fn(G) -> Result<(), (Fault<F>, G)>,
+{
+    type Good = G;
+}
+
+impl<P, G> Debug for FnProducer<P, G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnProducer")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, G> Display for FnProducer<P, G> {
+    /// Writes the name of the producer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<P, G, F> Producer for FnProducer<P, G>
+where
+    F: Flaws,
+    P: Fn(G) -> Result<(), (Fault<F>, G)>,
+{
+    type Flaws = F;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        if let Err((fault, good)) = (self.produce)(good) {
+            throw!(self.recall(fault, good));
+        }
+    }
+}
+
+/// A [`Consumer`] that retrieves goods by calling a closure.
+///
+/// Created by [`consumer_fn()`].
+pub struct FnConsumer<C> {
+    /// The name of the consumer.
+    name: String,
+    /// Retrieves a good.
+    consume: C,
+}
+
+impl<C, G, F> Agent for FnConsumer<C>
+where
+    F: Flaws,
+    C: Fn() -> Result<G, Fault<F>>,
+{
+    type Good = G;
+}
+
+impl<C, G, F> Consumer for FnConsumer<C>
+where
+    F: Flaws,
+    C: Fn() -> Result<G, Fault<F>>,
+{
+    type Flaws = F;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        (self.consume)().map_err(|fault| self.failure(fault))?
+    }
+}
+
+impl<C> Debug for FnConsumer<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnConsumer")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C> Display for FnConsumer<C> {
+    /// Writes the name of the consumer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
@@ -0,0 +1,165 @@
+//! Implements bridges between agents and [`Iterator`]s.
+#[cfg(feature = "alloc")]
+mod bridge;
+
+#[cfg(feature = "alloc")]
+pub use bridge::{ExtendProducer, IterConsumer};
+
+use {
+    crate::{Consumer, Failure, Flaws},
+    core::{
+        convert::TryFrom,
+        fmt::{self, Debug, Formatter},
+        iter::FusedIterator,
+    },
+};
+
+/// An [`Iterator`] that consumes goods from a [`Consumer`] without blocking.
+///
+/// Created by [`Consumer::goods()`]. A consumption that fails due to an insufficiency returns [`None`] without ending the iteration. The iteration ends when a consumption fails due to a defect; the [`Failure`] is then available from [`Goods::failure()`].
+pub struct Goods<'a, C: Consumer> {
+    /// The consumer of the goods.
+    consumer: &'a C,
+    /// The failure that ended the iteration.
+    failure: Option<Failure<C::Flaws>>,
+}
+
+impl<C> Debug for Goods<'_, C>
+where
+    C: Consumer + Debug,
+    Failure<C::Flaws>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Goods")
+            .field("consumer", &self.consumer)
+            .field("failure", &self.failure)
+            .finish()
+    }
+}
+
+impl<'a, C: Consumer> Goods<'a, C> {
+    /// Creates a new [`Goods`] that consumes from `consumer`.
+    pub(crate) fn new(consumer: &'a C) -> Self {
+        Self {
+            consumer,
+            failure: None,
+        }
+    }
+
+    /// Returns the [`Failure`] that ended the iteration, if it has ended.
+    pub fn failure(&self) -> Option<&Failure<C::Flaws>> {
+        self.failure.as_ref()
+    }
+
+    /// Converts `self` into the [`Failure`] that ended the iteration, if it has ended.
+    #[allow(clippy::missing_const_for_fn)] // Failure may not be dropped in a const fn.
+    pub fn into_failure(self) -> Option<Failure<C::Flaws>> {
+        self.failure
+    }
+}
+
+impl<C: Consumer> Iterator for Goods<'_, C> {
+    type Item = C::Good;
+
+    /// Consumes the next good, returning [`None`] if the consumption fails or a previous consumption failed due to a defect.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failure.is_some() {
+            return None;
+        }
+
+        match self.consumer.consume() {
+            Ok(good) => Some(good),
+            Err(failure) => {
+                if failure.is_defect() {
+                    self.failure = Some(failure);
+                }
+
+                None
+            }
+        }
+    }
+}
+
+/// An [`Iterator`] that demands goods from a [`Consumer`], blocking until each is available.
+///
+/// Created by [`Consumer::demands()`]. The iteration ends when a demand fails due to a defect; the [`Failure`] is then available from [`Demands::failure()`].
+pub struct Demands<'a, C>
+where
+    C: Consumer,
+    <C::Flaws as Flaws>::Defect: Flaws,
+{
+    /// The consumer of the goods.
+    consumer: &'a C,
+    /// The failure that ended the iteration.
+    failure: Option<Failure<<C::Flaws as Flaws>::Defect>>,
+}
+
+impl<C> Debug for Demands<'_, C>
+where
+    C: Consumer + Debug,
+    <C::Flaws as Flaws>::Defect: Flaws,
+    Failure<<C::Flaws as Flaws>::Defect>: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Demands")
+            .field("consumer", &self.consumer)
+            .field("failure", &self.failure)
+            .finish()
+    }
+}
+
+impl<'a, C: Consumer> Demands<'a, C>
+where
+    <C::Flaws as Flaws>::Defect: Flaws,
+{
+    /// Creates a new [`Demands`] that demands from `consumer`.
+    pub(crate) fn new(consumer: &'a C) -> Self {
+        Self {
+            consumer,
+            failure: None,
+        }
+    }
+
+    /// Returns the [`Failure`] that ended the iteration, if it has ended.
+    pub fn failure(&self) -> Option<&Failure<<C::Flaws as Flaws>::Defect>> {
+        self.failure.as_ref()
+    }
+
+    /// Converts `self` into the [`Failure`] that ended the iteration, if it has ended.
+    #[allow(clippy::missing_const_for_fn)] // Failure may not be dropped in a const fn.
+    pub fn into_failure(self) -> Option<Failure<<C::Flaws as Flaws>::Defect>> {
+        self.failure
+    }
+}
+
+impl<C: Consumer> Iterator for Demands<'_, C>
+where
+    <C::Flaws as Flaws>::Defect: Flaws<Defect = <C::Flaws as Flaws>::Defect>,
+    <<C::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+        TryFrom<<C::Flaws as Flaws>::Insufficiency>,
+{
+    type Item = C::Good;
+
+    /// Demands the next good, returning [`None`] once a demand has failed.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failure.is_some() {
+            return None;
+        }
+
+        match self.consumer.demand() {
+            Ok(good) => Some(good),
+            Err(failure) => {
+                self.failure = Some(failure);
+                None
+            }
+        }
+    }
+}
+
+impl<C: Consumer> FusedIterator for Demands<'_, C>
+where
+    <C::Flaws as Flaws>::Defect: Flaws<Defect = <C::Flaws as Flaws>::Defect>,
+    <<C::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+        TryFrom<<C::Flaws as Flaws>::Insufficiency>,
+{
+}
@@ -0,0 +1,126 @@
+//! Implements agents that retrieve goods from an [`Iterator`] or store goods into a collection.
+use {
+    crate::{
+        channel::WithdrawnSupply, Agent, Consumer, ConsumptionFlaws, EmptyStock, Failure, Fault,
+        Flawless, Producer, Recall,
+    },
+    alloc::string::{String, ToString},
+    core::{
+        cell::RefCell,
+        fmt::{self, Display, Formatter},
+        iter,
+        marker::PhantomData,
+    },
+    fehler::{throw, throws},
+};
+
+/// A [`Consumer`] that retrieves goods from an [`Iterator`].
+///
+/// Once the [`Iterator`] returns [`None`], consumption throws [`EmptyStock`] unless [`IterConsumer::withdraw_when_exhausted()`] has been called, in which case it throws [`WithdrawnSupply`].
+#[derive(Debug)]
+pub struct IterConsumer<I> {
+    /// The name of the consumer.
+    name: String,
+    /// The iterator of the goods.
+    goods: RefCell<I>,
+    /// If an exhausted iterator is a defect.
+    is_withdrawn_when_exhausted: bool,
+}
+
+impl<I> IterConsumer<I> {
+    /// Creates a new [`IterConsumer`] with `name` that retrieves goods from `goods`.
+    pub fn new<S, T>(name_str: &S, goods: T) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+        T: IntoIterator<IntoIter = I>,
+    {
+        Self {
+            name: name_str.as_ref().to_string(),
+            goods: RefCell::new(goods.into_iter()),
+            is_withdrawn_when_exhausted: false,
+        }
+    }
+
+    /// Sets `self` to throw [`WithdrawnSupply`] instead of [`EmptyStock`] when its goods are exhausted.
+    #[must_use]
+    pub const fn withdraw_when_exhausted(mut self) -> Self {
+        self.is_withdrawn_when_exhausted = true;
+        self
+    }
+}
+
+impl<I: Iterator> Agent for IterConsumer<I> {
+    type Good = I::Item;
+}
+
+impl<I: Iterator> Consumer for IterConsumer<I> {
+    type Flaws = ConsumptionFlaws<WithdrawnSupply>;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.goods.borrow_mut().next() {
+            Some(good) => good,
+            None if self.is_withdrawn_when_exhausted => {
+                throw!(self.failure(Fault::Defect(WithdrawnSupply)))
+            }
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+}
+
+impl<I> Display for IterConsumer<I> {
+    /// Writes the name of the consumer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// A [`Producer`] that stores goods into a collection that implements [`Extend`].
+#[derive(Debug)]
+pub struct ExtendProducer<E, G> {
+    /// The name of the producer.
+    name: String,
+    /// The collection of the goods.
+    goods: RefCell<E>,
+    /// The type of the goods.
+    good: PhantomData<fn(G)>,
+}
+
+impl<E, G> ExtendProducer<E, G> {
+    /// Creates a new [`ExtendProducer`] with `name` that stores goods into `goods`.
+    pub fn new<S>(name_str: &S, goods: E) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self {
+            name: name_str.as_ref().to_string(),
+            goods: RefCell::new(goods),
+            good: PhantomData,
+        }
+    }
+
+    /// Converts `self` into the collection of its goods.
+    pub fn into_inner(self) -> E {
+        self.goods.into_inner()
+    }
+}
+
+impl<E: Extend<G>, G> Agent for ExtendProducer<E, G> {
+    type Good = G;
+}
+
+impl<E, G> Display for ExtendProducer<E, G> {
+    /// Writes the name of the producer.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<E: Extend<G>, G> Producer for ExtendProducer<E, G> {
+    type Flaws = Flawless;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        self.goods.borrow_mut().extend(iter::once(good));
+    }
+}
@@ -8,18 +8,49 @@
 #![cfg_attr(feature = "unstable-doc-cfg", feature(doc_cfg))]
 #![no_std]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "alloc")]
+mod adapt;
+pub mod channel;
 mod error;
+#[cfg(feature = "alloc")]
+mod func;
+mod iter;
+pub mod queue;
+mod select;
+#[cfg(feature = "std")]
+mod sync;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "alloc")))]
+pub use adapt::{
+    Batched, Branch, ConsumerExt, Filter, FilterMap, Inspect, Map, MapInput, Merge, NoSources,
+    ProducerExt, Tee, TeeFlaws,
+};
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+pub use adapt::{BatchedTimeout, BatchingProducer};
 pub use error::{
     Blame, Blockage, ConsumptionFlaws, EmptyStock, Failure, FailureConversionError, Fault,
     FaultConversionError, Flawless, Flaws, FullStock, ProductionFlaws, Recall,
     RecallConversionError, TryBlame,
 };
-
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "alloc")))]
+pub use func::{consumer_fn, producer_fn, FnConsumer, FnProducer};
+pub use iter::{Demands, Goods};
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "alloc")))]
+pub use iter::{ExtendProducer, IterConsumer};
+#[doc(hidden)]
+pub use select::{poll as __poll, Arms as __Arms};
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
 use {
     core::{convert::TryFrom, fmt::Display},
     fehler::{throw, throws},
@@ -38,6 +69,8 @@ pub trait Producer: Agent {
     type Flaws: Flaws;
 
     /// Returns the [`Recall`] thrown by `self` when `fault` is caught while producing `good`.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "alloc")))]
     fn recall(
         &self,
         fault: Fault<Self::Flaws>,
@@ -165,6 +198,39 @@ pub trait Producer: Agent {
     }
 }
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "alloc")))]
+impl<A: Agent + ?Sized> Agent for Box<A> {
+    type Good = A::Good;
+}
+
+/// Allows [`Producer`]s of different types to be combined, such as by a [`Tee`] of `Box<dyn Producer>`.
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "alloc")))]
+impl<P> Producer for Box<P>
+where
+    P: Producer + ?Sized,
+    // Required to forward force to the boxed producer.
+    <P::Flaws as Flaws>::Defect: Flaws,
+{
+    type Flaws = P::Flaws;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        (**self).produce(good)?;
+    }
+
+    #[throws(Recall<<Self::Flaws as Flaws>::Defect, Self::Good>)]
+    fn force(&self, good: Self::Good)
+    where
+        <Self::Flaws as Flaws>::Defect: Flaws<Defect = <Self::Flaws as Flaws>::Defect>,
+        <<Self::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+            TryFrom<<Self::Flaws as Flaws>::Insufficiency>,
+    {
+        (**self).force(good)?;
+    }
+}
+
 /// Characterizes an agent that retrieves goods from a market.
 ///
 /// The order in which goods are retrieved is defined by the implementer.
@@ -173,6 +239,8 @@ pub trait Consumer: Agent {
     type Flaws: Flaws;
 
     /// Returns the [`Failure`] thrown by `self` when `fault` is caught.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "alloc")))]
     fn failure(&self, fault: Fault<Self::Flaws>) -> Failure<Self::Flaws> {
         Failure::new(&self, fault)
     }
@@ -211,109 +279,29 @@ pub trait Consumer: Agent {
             }
         }
     }
-}
-
-/// Defines traits of markets for a channel.
-///
-/// A channel exchanges goods between [`Producer`]s and [`Consumer`]s. If either all [`Consumer`]s or all [`Producer`]s for a channel are dropped, the channel becomes invalid.
-pub mod channel {
-    use {
-        super::{Consumer, ConsumptionFlaws, Flawless, Flaws, Producer, ProductionFlaws},
-        core::fmt::{self, Display, Formatter},
-    };
-
-    /// The defect thrown when a [`Producer`] attempts to produce to a channel with no [`Consumer`]s.
-    #[derive(Clone, Copy, Debug, Default)]
-    #[non_exhaustive]
-    pub struct WithdrawnDemand;
-
-    impl Display for WithdrawnDemand {
-        /// Writes "demand has withdrawn".
-        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-            write!(f, "demand has withdrawn")
-        }
-    }
-
-    #[cfg(feature = "std")]
-    #[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
-    impl std::error::Error for WithdrawnDemand {}
-
-    impl Flaws for WithdrawnDemand {
-        type Insufficiency = Flawless;
-        type Defect = Self;
-    }
-
-    /// The defect thrown when a [`Consumer`] attempts to consume from an empty channel with no [`Producer`]s.
-    #[derive(Clone, Copy, Debug, Default)]
-    #[non_exhaustive]
-    pub struct WithdrawnSupply;
-
-    impl Display for WithdrawnSupply {
-        /// Writes "supply has withdrawn".
-        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-            write!(f, "supply has withdrawn")
-        }
-    }
-
-    #[cfg(feature = "std")]
-    #[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
-    impl std::error::Error for WithdrawnSupply {}
-
-    impl Flaws for WithdrawnSupply {
-        type Insufficiency = Flawless;
-        type Defect = Self;
-    }
 
-    /// Characterizes a channel with infinite capacity.
-    pub trait InfiniteChannel<G> {
-        /// Specifies the [`Producer`].
-        type Producer: Producer<Good = G, Flaws = WithdrawnDemand>;
-        /// Specifies the [`Consumer`].
-        type Consumer: Consumer<Good = G, Flaws = ConsumptionFlaws<WithdrawnSupply>>;
-
-        /// Creates the [`Producer`] and [`Consumer`] connected to an infinite channel.
-        fn establish<S>(name_str: &S) -> (Self::Producer, Self::Consumer)
-        where
-            S: AsRef<str> + ?Sized;
-    }
-
-    /// Characterizes a channel with a limited capacity.
-    pub trait FiniteChannel<G> {
-        /// Specifies the [`Producer`].
-        type Producer: Producer<Good = G, Flaws = ProductionFlaws<WithdrawnDemand>>;
-        /// Specifies the [`Consumer`].
-        type Consumer: Consumer<Good = G, Flaws = ConsumptionFlaws<WithdrawnSupply>>;
-
-        /// Creates the [`Producer`] and [`Consumer`] connected to a channel with capacity of `size`.
-        fn establish<S>(name_str: &S, size: usize) -> (Self::Producer, Self::Consumer)
-        where
-            S: AsRef<str> + ?Sized;
-    }
-}
-
-/// Defines traits of markets for a queue.
-///
-/// A queue is a single item that implements [`Producer`] and [`Consumer`]. As a result, storing and retrieving from a queue cannot cause a defect.
-pub mod queue {
-    use super::{Consumer, EmptyStock, Flawless, FullStock, Producer};
-
-    /// Characterizes a queue with infinite size.
-    pub trait InfiniteQueue<G>:
-        Consumer<Good = G, Flaws = EmptyStock> + Producer<Good = G, Flaws = Flawless>
+    /// Returns an [`Iterator`] that consumes goods from `self` without blocking.
+    ///
+    /// The [`Iterator`] returns [`None`] when a consumption fails. It ends when a consumption fails due to a defect; the [`Failure`] is then available from [`Goods::failure()`].
+    fn goods(&self) -> Goods<'_, Self>
+    where
+        // Required for Consumer to be object safe: See https://doc.rust-lang.org/reference/items/traits.html#object-safety.
+        Self: Sized,
     {
-        /// Creates a queue with infinite size.
-        fn allocate<S>(name_str: &S) -> Self
-        where
-            S: AsRef<str> + ?Sized;
+        Goods::new(self)
     }
 
-    /// Characterizes a queue with a size.
-    pub trait FiniteQueue<G>:
-        Consumer<Good = G, Flaws = EmptyStock> + Producer<Good = G, Flaws = FullStock>
+    /// Returns an [`Iterator`] that demands goods from `self`, blocking until each is available.
+    ///
+    /// The [`Iterator`] ends when a demand fails due to a defect; the [`Failure`] is then available from [`Demands::failure()`].
+    fn demands(&self) -> Demands<'_, Self>
+    where
+        // Required for Consumer to be object safe: See https://doc.rust-lang.org/reference/items/traits.html#object-safety.
+        Self: Sized,
+        <Self::Flaws as Flaws>::Defect: Flaws<Defect = <Self::Flaws as Flaws>::Defect>,
+        <<Self::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+            TryFrom<<Self::Flaws as Flaws>::Insufficiency>,
     {
-        /// Creates a queue with finite size.
-        fn allocate<S>(name_str: &S, size: usize) -> Self
-        where
-            S: AsRef<str> + ?Sized;
+        Demands::new(self)
     }
 }
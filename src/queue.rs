@@ -0,0 +1,70 @@
+//! Defines traits of markets for a queue.
+//!
+//! A queue is a single item that implements [`Producer`] and [`Consumer`]. As a result, storing and retrieving from a queue cannot cause a defect.
+#[cfg(feature = "std")]
+mod dedup;
+#[cfg(feature = "std")]
+mod delay;
+#[cfg(feature = "alloc")]
+mod deque;
+#[cfg(feature = "alloc")]
+mod priority;
+mod ring;
+#[cfg(feature = "alloc")]
+mod stack;
+
+pub use ring::RingQueue;
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+pub use {
+    dedup::{DedupFlaws, DedupInsufficiency, DedupQueue, Duplicate, OnDuplicate},
+    delay::{DelayQueue, InvalidDeadline},
+};
+#[cfg(feature = "alloc")]
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "alloc")))]
+pub use {
+    deque::{BoundedQueue, UnboundedQueue},
+    priority::{BoundedPriorityQueue, PriorityQueue},
+    stack::{BoundedStack, Stack},
+};
+
+use super::{Consumer, EmptyStock, Flawless, FullStock, Producer};
+
+/// Characterizes a queue with infinite size.
+pub trait InfiniteQueue<G>:
+    Consumer<Good = G, Flaws = EmptyStock> + Producer<Good = G, Flaws = Flawless>
+{
+    /// Creates a queue with infinite size.
+    fn allocate<S>(name_str: &S) -> Self
+    where
+        S: AsRef<str> + ?Sized;
+}
+
+/// Characterizes a queue with a size.
+pub trait FiniteQueue<G>:
+    Consumer<Good = G, Flaws = EmptyStock> + Producer<Good = G, Flaws = FullStock>
+{
+    /// Creates a queue with finite size.
+    fn allocate<S>(name_str: &S, size: usize) -> Self
+    where
+        S: AsRef<str> + ?Sized;
+}
+
+/// Defines how a bounded market handles a good that is stored while it is full.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Overflow {
+    /// The new good is rejected by throwing [`FullStock`].
+    Reject,
+    /// The new good is dropped as if it had been stored and immediately evicted.
+    DropNewest,
+    /// The oldest good is evicted to make space for the new good.
+    DropOldest,
+}
+
+impl Default for Overflow {
+    /// Returns [`Overflow::Reject`].
+    fn default() -> Self {
+        Self::Reject
+    }
+}
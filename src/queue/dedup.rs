@@ -0,0 +1,241 @@
+//! Implements a queue wrapper that does not store goods already in stock.
+use {
+    super::BoundedQueue,
+    crate::{sync::lock, Agent, Consumer, Failure, Fault, Flawless, Flaws, Producer, Recall},
+    core::{
+        convert::TryFrom,
+        fmt::{self, Display, Formatter},
+        hash::Hash,
+        marker::PhantomData,
+    },
+    fehler::{throw, throws},
+    std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+    },
+};
+
+/// Defines how a [`DedupQueue`] handles a good whose key matches that of a good already in stock.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum OnDuplicate {
+    /// The good is dropped and production succeeds.
+    Ignore,
+    /// The good is rejected by throwing [`Duplicate`].
+    Reject,
+}
+
+impl Default for OnDuplicate {
+    /// Returns [`OnDuplicate::Ignore`].
+    fn default() -> Self {
+        Self::Ignore
+    }
+}
+
+/// Wraps a queue so that a good is not stored while a good with the same key is in stock.
+///
+/// The key of a good is the good itself for [`DedupQueue::new()`] or is defined by the function given to [`DedupQueue::by_key()`]. A key is released when its good is retrieved, after which a good with that key may be stored again. If the wrapped queue is a [`BoundedQueue`] that drops goods due to overflow, [`DedupQueue::release_evicted()`] also releases the keys of the dropped goods.
+///
+/// The keys are guarded by a [`Mutex`], so a [`DedupQueue`] is [`Sync`] whenever the wrapped queue and key function are.
+#[derive(Debug)]
+pub struct DedupQueue<Q, K, F> {
+    /// The wrapped queue.
+    queue: Q,
+    /// Returns the key of a good.
+    key: Arc<F>,
+    /// The keys of the goods in stock.
+    keys: Arc<Mutex<HashSet<K>>>,
+    /// How a duplicate good is handled.
+    on_duplicate: OnDuplicate,
+}
+
+impl<Q, G> DedupQueue<Q, G, fn(&G) -> G>
+where
+    Q: Agent<Good = G>,
+    G: Clone + Eq + Hash,
+{
+    /// Creates a [`DedupQueue`] that wraps `queue` and uses each good as its own key.
+    pub fn new(queue: Q) -> Self {
+        Self::by_key(queue, G::clone)
+    }
+}
+
+impl<Q, K, F> DedupQueue<Q, K, F>
+where
+    Q: Agent,
+    K: Eq + Hash,
+    F: Fn(&Q::Good) -> K,
+{
+    /// Creates a [`DedupQueue`] that wraps `queue` and uses `key` to determine the key of each good.
+    pub fn by_key(queue: Q, key: F) -> Self {
+        Self {
+            queue,
+            key: Arc::new(key),
+            keys: Arc::new(Mutex::new(HashSet::new())),
+            on_duplicate: OnDuplicate::Ignore,
+        }
+    }
+
+    /// Releases the key of `good`, which is no longer in stock.
+    fn release(&self, good: &Q::Good) {
+        let _ = lock(&self.keys).remove(&(self.key)(good));
+    }
+
+    /// Sets how `self` handles a duplicate good.
+    #[must_use]
+    pub fn on_duplicate(mut self, on_duplicate: OnDuplicate) -> Self {
+        self.on_duplicate = on_duplicate;
+        self
+    }
+}
+
+impl<G, K, F> DedupQueue<BoundedQueue<G>, K, F>
+where
+    G: 'static,
+    K: Eq + Hash + Send + 'static,
+    F: Fn(&G) -> K + Send + Sync + 'static,
+{
+    /// Releases the key of each good that the wrapped [`BoundedQueue`] drops due to overflow.
+    ///
+    /// The key is released before the good is passed to the callback set by [`BoundedQueue::evict_with()`], if any.
+    #[must_use]
+    pub fn release_evicted(mut self) -> Self {
+        let key = Arc::clone(&self.key);
+        let keys = Arc::clone(&self.keys);
+
+        self.queue = self.queue.inspect_evicted(move |good| {
+            let _ = lock(&keys).remove(&key(good));
+        });
+        self
+    }
+}
+
+impl<Q: Agent, K, F> Agent for DedupQueue<Q, K, F> {
+    type Good = Q::Good;
+}
+
+impl<Q, K, F> Consumer for DedupQueue<Q, K, F>
+where
+    Q: Consumer,
+    // Required to forward demand to the wrapped queue.
+    <Q::Flaws as Flaws>::Defect: Flaws,
+    K: Eq + Hash,
+    F: Fn(&Q::Good) -> K,
+{
+    type Flaws = Q::Flaws;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        let good = self.queue.consume()?;
+
+        self.release(&good);
+        good
+    }
+
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good
+    where
+        <Self::Flaws as Flaws>::Defect: Flaws<Defect = <Self::Flaws as Flaws>::Defect>,
+        <<Self::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+            TryFrom<<Self::Flaws as Flaws>::Insufficiency>,
+    {
+        let good = self.queue.demand()?;
+
+        self.release(&good);
+        good
+    }
+}
+
+impl<Q: Display, K, F> Display for DedupQueue<Q, K, F> {
+    /// Writes the name of the wrapped queue.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.queue)
+    }
+}
+
+impl<Q, K, F> Producer for DedupQueue<Q, K, F>
+where
+    Q: Producer,
+    K: Eq + Hash,
+    F: Fn(&Q::Good) -> K,
+{
+    type Flaws = DedupFlaws<Q::Flaws>;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        // Record the key before storing so that the eviction callback is able to release it if the wrapped queue drops good.
+        if lock(&self.keys).insert((self.key)(&good)) {
+            if let Err(recall) = self.queue.produce(good) {
+                let (failure, good) = recall.into_parts();
+
+                self.release(&good);
+                throw!(Recall::new(
+                    failure.map_insufficiency(DedupInsufficiency::Stock),
+                    good
+                ));
+            }
+        } else if self.on_duplicate == OnDuplicate::Reject {
+            throw!(self.recall(
+                Fault::Insufficiency(DedupInsufficiency::Duplicate(Duplicate)),
+                good
+            ));
+        }
+    }
+}
+
+/// Specifies the [`Flaws`] of a [`DedupQueue`] wrapping a [`Producer`] with [`Flaws`] `F`.
+#[derive(Debug)]
+pub struct DedupFlaws<F> {
+    /// The flaws of the wrapped [`Producer`].
+    flaws: PhantomData<F>,
+}
+
+impl<F: Flaws> Flaws for DedupFlaws<F> {
+    type Insufficiency = DedupInsufficiency<F::Insufficiency>;
+    type Defect = F::Defect;
+}
+
+/// The insufficiency thrown when a [`DedupQueue`] fails to store a good.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum DedupInsufficiency<I> {
+    /// The wrapped queue threw an insufficiency.
+    Stock(I),
+    /// A good with the same key is already in stock.
+    Duplicate(Duplicate),
+}
+
+impl<I: Display> Display for DedupInsufficiency<I> {
+    /// Writes the display of the insufficiency.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Stock(ref insufficiency) => write!(f, "{}", insufficiency),
+            Self::Duplicate(ref duplicate) => write!(f, "{}", duplicate),
+        }
+    }
+}
+
+impl<I> TryFrom<DedupInsufficiency<I>> for Flawless {
+    type Error = ();
+
+    fn try_from(_: DedupInsufficiency<I>) -> Result<Self, Self::Error> {
+        Err(())
+    }
+}
+
+/// The insufficiency thrown when a [`DedupQueue`] that rejects duplicates is given a good whose key is already in stock.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Duplicate;
+
+impl Display for Duplicate {
+    /// Writes "uniqueness".
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "uniqueness")
+    }
+}
+
+impl Flaws for Duplicate {
+    type Insufficiency = Self;
+    type Defect = Flawless;
+}
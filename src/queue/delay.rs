@@ -0,0 +1,222 @@
+//! Implements an [`InfiniteQueue`] whose goods become available after a deadline.
+use {
+    super::InfiniteQueue,
+    crate::{
+        sync::lock, Agent, Consumer, EmptyStock, Failure, Fault, Flawless, Flaws, Producer, Recall,
+    },
+    core::{
+        cmp::Ordering,
+        fmt::{self, Display, Formatter},
+        time::Duration,
+    },
+    fehler::{throw, throws},
+    std::{
+        collections::BinaryHeap,
+        string::{String, ToString},
+        sync::{Condvar, Mutex, PoisonError},
+        time::Instant,
+    },
+};
+
+/// An [`InfiniteQueue`] that holds each good until its deadline has passed.
+///
+/// Goods stored by [`Producer::produce()`] are available immediately; [`DelayQueue::produce_at()`] and [`DelayQueue::produce_after()`] store goods that become available later. Available goods are retrieved in order of their deadlines, with equal deadlines retrieved in the order they were stored.
+///
+/// A [`DelayQueue`] may be shared between threads, allowing one thread to [`Consumer::demand()`] while others produce.
+#[derive(Debug)]
+pub struct DelayQueue<G> {
+    /// The name of the queue.
+    name: String,
+    /// The goods ordered by deadline.
+    schedule: Mutex<Schedule<G>>,
+    /// Notified when a good is stored.
+    has_goods: Condvar,
+}
+
+impl<G> DelayQueue<G> {
+    /// Stores `good` so that it becomes available at `deadline`.
+    pub fn produce_at(&self, deadline: Instant, good: G) {
+        let mut schedule = lock(&self.schedule);
+        let sequence = schedule.sequence;
+
+        schedule.sequence = sequence.wrapping_add(1);
+        schedule.entries.push(Entry {
+            deadline,
+            sequence,
+            good,
+        });
+        self.has_goods.notify_all();
+    }
+
+    /// Stores `good` so that it becomes available once `delay` has elapsed.
+    ///
+    /// # Errors
+    ///
+    /// If the deadline after `delay` is not able to be represented by an [`Instant`], `produce_after` shall throw a [`Recall`] containing [`InvalidDeadline`] and `good`.
+    #[throws(Recall<InvalidDeadline, G>)]
+    pub fn produce_after(&self, delay: Duration, good: G) {
+        match Instant::now().checked_add(delay) {
+            Some(deadline) => self.produce_at(deadline, good),
+            None => throw!(Recall::new(
+                Failure::new(self, Fault::Defect(InvalidDeadline)),
+                good
+            )),
+        }
+    }
+}
+
+impl<G> Agent for DelayQueue<G> {
+    type Good = G;
+}
+
+impl<G> Consumer for DelayQueue<G> {
+    type Flaws = EmptyStock;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match lock(&self.schedule).pop_due(Instant::now()) {
+            Some(good) => good,
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+
+    /// Retrieves the next available good, sleeping until the earliest deadline has passed.
+    ///
+    /// # Errors
+    ///
+    /// `demand` shall not throw since a [`DelayQueue`] cannot cause a defect.
+    #[throws(Failure<<Self::Flaws as Flaws>::Defect>)]
+    fn demand(&self) -> Self::Good {
+        let mut schedule = lock(&self.schedule);
+
+        loop {
+            let now = Instant::now();
+
+            if let Some(good) = schedule.pop_due(now) {
+                break good;
+            }
+
+            schedule = match schedule.entries.peek() {
+                Some(entry) => {
+                    let timeout = entry.deadline.saturating_duration_since(now);
+
+                    self.has_goods
+                        .wait_timeout(schedule, timeout)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .has_goods
+                    .wait(schedule)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
+
+impl<G> Display for DelayQueue<G> {
+    /// Writes the name of the queue.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> InfiniteQueue<G> for DelayQueue<G> {
+    fn allocate<S>(name_str: &S) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self {
+            name: name_str.as_ref().to_string(),
+            schedule: Mutex::new(Schedule {
+                entries: BinaryHeap::new(),
+                sequence: 0,
+            }),
+            has_goods: Condvar::new(),
+        }
+    }
+}
+
+impl<G> Producer for DelayQueue<G> {
+    type Flaws = Flawless;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        self.produce_at(Instant::now(), good);
+    }
+}
+
+/// The defect thrown when the deadline of a good stored into a [`DelayQueue`] is not able to be represented.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct InvalidDeadline;
+
+impl Display for InvalidDeadline {
+    /// Writes "deadline is out of range".
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "deadline is out of range")
+    }
+}
+
+#[cfg_attr(feature = "unstable-doc-cfg", doc(cfg(feature = "std")))]
+impl std::error::Error for InvalidDeadline {}
+
+impl Flaws for InvalidDeadline {
+    type Insufficiency = Flawless;
+    type Defect = Self;
+}
+
+/// The goods of a [`DelayQueue`] ordered by deadline.
+#[derive(Debug)]
+struct Schedule<G> {
+    /// The entries of the schedule, with the earliest deadline at the top.
+    entries: BinaryHeap<Entry<G>>,
+    /// The sequence number of the next good to be stored.
+    sequence: u64,
+}
+
+impl<G> Schedule<G> {
+    /// Removes and returns the good with the earliest deadline if that deadline is not after `now`.
+    fn pop_due(&mut self, now: Instant) -> Option<G> {
+        if self.entries.peek()?.deadline <= now {
+            self.entries.pop().map(|entry| entry.good)
+        } else {
+            None
+        }
+    }
+}
+
+/// A good stored in a [`Schedule`].
+#[derive(Debug)]
+struct Entry<G> {
+    /// When the good becomes available.
+    deadline: Instant,
+    /// The sequence number at which the good was stored.
+    sequence: u64,
+    /// The good.
+    good: G,
+}
+
+impl<G> Eq for Entry<G> {}
+
+impl<G> Ord for Entry<G> {
+    /// Orders earlier deadlines, and then earlier sequence numbers, as greater so that they are at the top of the heap.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .deadline
+            .cmp(&self.deadline)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl<G> PartialEq for Entry<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<G> PartialOrd for Entry<G> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -0,0 +1,208 @@
+//! Implements [`InfiniteQueue`] and [`FiniteQueue`] using a [`VecDeque`].
+use {
+    super::{FiniteQueue, InfiniteQueue, Overflow},
+    crate::{Agent, Consumer, EmptyStock, Failure, Fault, Flawless, FullStock, Producer, Recall},
+    alloc::{
+        boxed::Box,
+        collections::VecDeque,
+        string::{String, ToString},
+    },
+    core::{
+        cell::RefCell,
+        fmt::{self, Debug, Display, Formatter},
+    },
+    fehler::{throw, throws},
+};
+
+/// An [`InfiniteQueue`] that retrieves goods in the order they were stored.
+#[derive(Debug)]
+pub struct UnboundedQueue<G> {
+    /// The name of the queue.
+    name: String,
+    /// The goods in the order they were stored.
+    goods: RefCell<VecDeque<G>>,
+}
+
+impl<G> Agent for UnboundedQueue<G> {
+    type Good = G;
+}
+
+impl<G> Consumer for UnboundedQueue<G> {
+    type Flaws = EmptyStock;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.goods.borrow_mut().pop_front() {
+            Some(good) => good,
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+}
+
+impl<G> Display for UnboundedQueue<G> {
+    /// Writes the name of the queue.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> InfiniteQueue<G> for UnboundedQueue<G> {
+    fn allocate<S>(name_str: &S) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self {
+            name: name_str.as_ref().to_string(),
+            goods: RefCell::new(VecDeque::new()),
+        }
+    }
+}
+
+impl<G> Producer for UnboundedQueue<G> {
+    type Flaws = Flawless;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        self.goods.borrow_mut().push_back(good);
+    }
+}
+
+/// A [`FiniteQueue`] that retrieves goods in the order they were stored.
+///
+/// When full, the queue handles a new good as defined by its [`Overflow`]. Goods dropped due to overflow are passed to the callback set by [`BoundedQueue::evict_with()`], if any.
+pub struct BoundedQueue<G> {
+    /// The name of the queue.
+    name: String,
+    /// The goods in the order they were stored.
+    goods: RefCell<VecDeque<G>>,
+    /// The maximum number of goods.
+    size: usize,
+    /// How a good stored while the queue is full is handled.
+    overflow: Overflow,
+    /// Receives goods that are dropped due to overflow.
+    evict: Option<Box<dyn Fn(G) + Send + Sync>>,
+}
+
+impl<G> BoundedQueue<G> {
+    /// Creates a queue of `size` that handles a good stored while full as defined by `overflow`.
+    pub fn with_overflow<S>(name_str: &S, size: usize, overflow: Overflow) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self {
+            name: name_str.as_ref().to_string(),
+            goods: RefCell::new(VecDeque::with_capacity(size)),
+            size,
+            overflow,
+            evict: None,
+        }
+    }
+
+    /// Sets `evict` to receive each good that is dropped due to overflow.
+    #[must_use]
+    pub fn evict_with<F>(mut self, evict: F) -> Self
+    where
+        F: Fn(G) + Send + Sync + 'static,
+    {
+        self.evict = Some(Box::new(evict));
+        self
+    }
+
+    /// Sets `evict` to inspect each good that is dropped due to overflow before the good is passed to the callback that was previously set, if any.
+    #[cfg(feature = "std")]
+    pub(crate) fn inspect_evicted<F>(mut self, evict: F) -> Self
+    where
+        F: Fn(&G) + Send + Sync + 'static,
+        G: 'static,
+    {
+        let previous = self.evict.take();
+
+        self.evict_with(move |good| {
+            evict(&good);
+
+            if let Some(ref previous) = previous {
+                previous(good);
+            }
+        })
+    }
+
+    /// Passes `good` to the eviction callback of `self`, if any.
+    fn evict(&self, good: G) {
+        if let Some(ref evict) = self.evict {
+            evict(good);
+        }
+    }
+}
+
+impl<G> Agent for BoundedQueue<G> {
+    type Good = G;
+}
+
+impl<G> Consumer for BoundedQueue<G> {
+    type Flaws = EmptyStock;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.goods.borrow_mut().pop_front() {
+            Some(good) => good,
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+}
+
+impl<G: Debug> Debug for BoundedQueue<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedQueue")
+            .field("name", &self.name)
+            .field("goods", &self.goods)
+            .field("size", &self.size)
+            .field("overflow", &self.overflow)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G> Display for BoundedQueue<G> {
+    /// Writes the name of the queue.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> FiniteQueue<G> for BoundedQueue<G> {
+    fn allocate<S>(name_str: &S, size: usize) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self::with_overflow(name_str, size, Overflow::Reject)
+    }
+}
+
+impl<G> Producer for BoundedQueue<G> {
+    type Flaws = FullStock;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        let mut goods = self.goods.borrow_mut();
+
+        if goods.len() >= self.size {
+            let dropped = match self.overflow {
+                Overflow::Reject => throw!(self.recall(Fault::Insufficiency(FullStock), good)),
+                Overflow::DropNewest => good,
+                Overflow::DropOldest => match goods.pop_front() {
+                    Some(oldest) => {
+                        goods.push_back(good);
+                        oldest
+                    }
+                    // A queue of size 0 has no oldest good to evict.
+                    None => good,
+                },
+            };
+
+            // Release the goods so that the callback is able to act upon the queue.
+            drop(goods);
+            self.evict(dropped);
+        } else {
+            goods.push_back(good);
+        }
+    }
+}
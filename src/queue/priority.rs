@@ -0,0 +1,315 @@
+//! Implements [`InfiniteQueue`] and [`FiniteQueue`] using a binary heap.
+use {
+    super::{FiniteQueue, InfiniteQueue},
+    crate::{Agent, Consumer, EmptyStock, Failure, Fault, Flawless, FullStock, Producer, Recall},
+    alloc::{
+        collections::BinaryHeap,
+        string::{String, ToString},
+        sync::Arc,
+    },
+    core::{
+        cell::RefCell,
+        cmp::Ordering,
+        fmt::{self, Debug, Display, Formatter},
+    },
+    fehler::{throw, throws},
+};
+
+/// Compares the priorities of 2 goods by their [`Ord`] implementation.
+type Natural<G> = fn(&G, &G) -> Ordering;
+
+/// An [`InfiniteQueue`] that retrieves the good with the greatest priority as defined by `O`.
+///
+/// By default the priority of a good is defined by its [`Ord`] implementation; [`PriorityQueue::by_key()`] and [`PriorityQueue::by()`] define other priorities. Goods with equal priority are retrieved in the order they were stored.
+pub struct PriorityQueue<G, O = Natural<G>> {
+    /// The name of the queue.
+    name: String,
+    /// The goods ordered by priority.
+    heap: RefCell<Heap<G, O>>,
+}
+
+impl<G> PriorityQueue<G> {
+    /// Creates a queue that retrieves the good with the greatest key returned by `f`.
+    pub fn by_key<S, K, F>(name_str: &S, f: F) -> PriorityQueue<G, impl Fn(&G, &G) -> Ordering>
+    where
+        S: AsRef<str> + ?Sized,
+        K: Ord,
+        F: Fn(&G) -> K,
+    {
+        PriorityQueue::by(name_str, move |a: &G, b: &G| f(a).cmp(&f(b)))
+    }
+
+    /// Creates a queue that retrieves the greatest good as defined by `compare`.
+    pub fn by<S, O>(name_str: &S, compare: O) -> PriorityQueue<G, O>
+    where
+        S: AsRef<str> + ?Sized,
+        O: Fn(&G, &G) -> Ordering,
+    {
+        PriorityQueue {
+            name: name_str.as_ref().to_string(),
+            heap: RefCell::new(Heap::new(compare, 0)),
+        }
+    }
+}
+
+impl<G, O> Agent for PriorityQueue<G, O> {
+    type Good = G;
+}
+
+impl<G, O> Consumer for PriorityQueue<G, O>
+where
+    O: Fn(&G, &G) -> Ordering,
+{
+    type Flaws = EmptyStock;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.heap.borrow_mut().pop() {
+            Some(good) => good,
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+}
+
+impl<G: Debug, O> Debug for PriorityQueue<G, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PriorityQueue")
+            .field("name", &self.name)
+            .field("heap", &self.heap)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G, O> Display for PriorityQueue<G, O> {
+    /// Writes the name of the queue.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G: Ord> InfiniteQueue<G> for PriorityQueue<G> {
+    fn allocate<S>(name_str: &S) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self::by(name_str, G::cmp)
+    }
+}
+
+impl<G, O> Producer for PriorityQueue<G, O>
+where
+    O: Fn(&G, &G) -> Ordering,
+{
+    type Flaws = Flawless;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        self.heap.borrow_mut().push(good);
+    }
+}
+
+/// A [`FiniteQueue`] that retrieves the good with the greatest priority as defined by `O`.
+///
+/// Priorities are defined as they are for a [`PriorityQueue`].
+pub struct BoundedPriorityQueue<G, O = Natural<G>> {
+    /// The name of the queue.
+    name: String,
+    /// The goods ordered by priority.
+    heap: RefCell<Heap<G, O>>,
+    /// The maximum number of goods.
+    size: usize,
+}
+
+impl<G> BoundedPriorityQueue<G> {
+    /// Creates a queue of `size` that retrieves the good with the greatest key returned by `f`.
+    pub fn by_key<S, K, F>(
+        name_str: &S,
+        size: usize,
+        f: F,
+    ) -> BoundedPriorityQueue<G, impl Fn(&G, &G) -> Ordering>
+    where
+        S: AsRef<str> + ?Sized,
+        K: Ord,
+        F: Fn(&G) -> K,
+    {
+        BoundedPriorityQueue::by(name_str, size, move |a: &G, b: &G| f(a).cmp(&f(b)))
+    }
+
+    /// Creates a queue of `size` that retrieves the greatest good as defined by `compare`.
+    pub fn by<S, O>(name_str: &S, size: usize, compare: O) -> BoundedPriorityQueue<G, O>
+    where
+        S: AsRef<str> + ?Sized,
+        O: Fn(&G, &G) -> Ordering,
+    {
+        BoundedPriorityQueue {
+            name: name_str.as_ref().to_string(),
+            heap: RefCell::new(Heap::new(compare, size)),
+            size,
+        }
+    }
+}
+
+impl<G, O> Agent for BoundedPriorityQueue<G, O> {
+    type Good = G;
+}
+
+impl<G, O> Consumer for BoundedPriorityQueue<G, O>
+where
+    O: Fn(&G, &G) -> Ordering,
+{
+    type Flaws = EmptyStock;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.heap.borrow_mut().pop() {
+            Some(good) => good,
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+}
+
+impl<G: Debug, O> Debug for BoundedPriorityQueue<G, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundedPriorityQueue")
+            .field("name", &self.name)
+            .field("heap", &self.heap)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G, O> Display for BoundedPriorityQueue<G, O> {
+    /// Writes the name of the queue.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G: Ord> FiniteQueue<G> for BoundedPriorityQueue<G> {
+    fn allocate<S>(name_str: &S, size: usize) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self::by(name_str, size, G::cmp)
+    }
+}
+
+impl<G, O> Producer for BoundedPriorityQueue<G, O>
+where
+    O: Fn(&G, &G) -> Ordering,
+{
+    type Flaws = FullStock;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        let mut heap = self.heap.borrow_mut();
+
+        if heap.len() >= self.size {
+            throw!(self.recall(Fault::Insufficiency(FullStock), good));
+        }
+
+        heap.push(good);
+    }
+}
+
+/// A max-heap of goods that breaks ties by the order in which goods were stored.
+struct Heap<G, O> {
+    /// The entries of the heap.
+    entries: BinaryHeap<Entry<G, O>>,
+    /// Compares the priorities of goods; shared with each entry so that [`BinaryHeap`] is able to order the entries.
+    order: Arc<O>,
+    /// The sequence number of the next good to be stored.
+    sequence: u64,
+}
+
+impl<G, O> Heap<G, O>
+where
+    O: Fn(&G, &G) -> Ordering,
+{
+    /// Creates a new [`Heap`] ordered by `order` with space for `capacity` goods.
+    fn new(order: O, capacity: usize) -> Self {
+        Self {
+            entries: BinaryHeap::with_capacity(capacity),
+            order: Arc::new(order),
+            sequence: 0,
+        }
+    }
+
+    /// Returns the number of goods in `self`.
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Stores `good` in `self`.
+    fn push(&mut self, good: G) {
+        self.entries.push(Entry {
+            good,
+            sequence: self.sequence,
+            order: Arc::clone(&self.order),
+        });
+        self.sequence = self.sequence.wrapping_add(1);
+    }
+
+    /// Removes and returns the good with the greatest priority.
+    fn pop(&mut self) -> Option<G> {
+        self.entries.pop().map(|entry| entry.good)
+    }
+}
+
+impl<G: Debug, O> Debug for Heap<G, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Heap")
+            .field("entries", &self.entries)
+            .field("sequence", &self.sequence)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A good stored in a [`Heap`].
+struct Entry<G, O> {
+    /// The good.
+    good: G,
+    /// The sequence number at which `good` was stored.
+    sequence: u64,
+    /// Compares the priorities of goods.
+    order: Arc<O>,
+}
+
+impl<G: Debug, O> Debug for Entry<G, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("good", &self.good)
+            .field("sequence", &self.sequence)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G, O> Eq for Entry<G, O> where O: Fn(&G, &G) -> Ordering {}
+
+impl<G, O> Ord for Entry<G, O>
+where
+    O: Fn(&G, &G) -> Ordering,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Earlier goods are greater so that equal priorities are retrieved in the order they were stored.
+        (self.order)(&self.good, &other.good).then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+impl<G, O> PartialEq for Entry<G, O>
+where
+    O: Fn(&G, &G) -> Ordering,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<G, O> PartialOrd for Entry<G, O>
+where
+    O: Fn(&G, &G) -> Ordering,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -0,0 +1,124 @@
+//! Implements a queue that stores its goods inline.
+use {
+    crate::{Agent, Consumer, EmptyStock, Failure, Fault, FullStock, Producer, Recall},
+    core::{
+        cell::RefCell,
+        fmt::{self, Display, Formatter},
+    },
+    fehler::{throw, throws},
+};
+
+/// A queue with a size of `N` that stores its goods inline.
+///
+/// Goods are retrieved in the order they were stored. Unlike the queues that implement [`FiniteQueue`](super::FiniteQueue), a [`RingQueue`] holds its goods in an array within the struct, so its size is known at compile time and its name must be `'static`. Neither storing and retrieving goods nor throwing a [`Failure`] or [`Recall`] allocates, so a [`RingQueue`] is available without the `alloc` feature.
+#[derive(Debug)]
+pub struct RingQueue<G, const N: usize> {
+    /// The name of the queue.
+    name: &'static str,
+    /// The ring holding the goods.
+    ring: RefCell<Ring<G, N>>,
+}
+
+impl<G, const N: usize> RingQueue<G, N> {
+    /// Signifies a slot that does not hold a good.
+    const VACANT: Option<G> = None;
+
+    /// Creates a new [`RingQueue`] with `name`.
+    #[must_use]
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            ring: RefCell::new(Ring {
+                slots: [Self::VACANT; N],
+                head: 0,
+                len: 0,
+            }),
+        }
+    }
+}
+
+impl<G, const N: usize> Agent for RingQueue<G, N> {
+    type Good = G;
+}
+
+impl<G, const N: usize> Consumer for RingQueue<G, N> {
+    type Flaws = EmptyStock;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.ring.borrow_mut().pop() {
+            Some(good) => good,
+            None => throw!(Failure::with_name(
+                self.name,
+                Fault::Insufficiency(EmptyStock)
+            )),
+        }
+    }
+}
+
+impl<G, const N: usize> Display for RingQueue<G, N> {
+    /// Writes the name of the queue.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G, const N: usize> Producer for RingQueue<G, N> {
+    type Flaws = FullStock;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        if let Err(good) = self.ring.borrow_mut().push(good) {
+            throw!(Recall::new(
+                Failure::with_name(self.name, Fault::Insufficiency(FullStock)),
+                good
+            ));
+        }
+    }
+}
+
+/// A circular buffer of `N` slots.
+#[derive(Debug)]
+struct Ring<G, const N: usize> {
+    /// The slots of the ring.
+    slots: [Option<G>; N],
+    /// The index of the slot holding the oldest good.
+    head: usize,
+    /// The number of goods in the ring.
+    len: usize,
+}
+
+impl<G, const N: usize> Ring<G, N> {
+    /// Stores `good` after the newest good, returning `good` if the ring is full.
+    fn push(&mut self, good: G) -> Result<(), G> {
+        if self.len >= N {
+            return Err(good);
+        }
+
+        match self.slot(self.len) {
+            Some(slot) => {
+                *slot = Some(good);
+                self.len = self.len.saturating_add(1);
+                Ok(())
+            }
+            None => Err(good),
+        }
+    }
+
+    /// Removes and returns the oldest good.
+    fn pop(&mut self) -> Option<G> {
+        let good = self.slot(0)?.take()?;
+
+        // head is less than N, so incrementing it cannot overflow.
+        self.head = self.head.wrapping_add(1).checked_rem(N).unwrap_or(0);
+        self.len = self.len.saturating_sub(1);
+        Some(good)
+    }
+
+    /// Returns the slot that is `offset` slots after the slot holding the oldest good.
+    fn slot(&mut self, offset: usize) -> Option<&mut Option<G>> {
+        let index = self.head.checked_add(offset)?.checked_rem(N)?;
+
+        self.slots.get_mut(index)
+    }
+}
@@ -0,0 +1,129 @@
+//! Implements [`InfiniteQueue`] and [`FiniteQueue`] that retrieve the most recently stored good.
+use {
+    super::{FiniteQueue, InfiniteQueue},
+    crate::{Agent, Consumer, EmptyStock, Failure, Fault, Flawless, FullStock, Producer, Recall},
+    alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    },
+    core::{
+        cell::RefCell,
+        fmt::{self, Display, Formatter},
+    },
+    fehler::{throw, throws},
+};
+
+/// An [`InfiniteQueue`] that retrieves the most recently stored good first.
+#[derive(Debug)]
+pub struct Stack<G> {
+    /// The name of the stack.
+    name: String,
+    /// The goods in the order they were stored, with the most recent at the end.
+    goods: RefCell<Vec<G>>,
+}
+
+impl<G> Agent for Stack<G> {
+    type Good = G;
+}
+
+impl<G> Consumer for Stack<G> {
+    type Flaws = EmptyStock;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.goods.borrow_mut().pop() {
+            Some(good) => good,
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+}
+
+impl<G> Display for Stack<G> {
+    /// Writes the name of the stack.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> InfiniteQueue<G> for Stack<G> {
+    fn allocate<S>(name_str: &S) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self {
+            name: name_str.as_ref().to_string(),
+            goods: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl<G> Producer for Stack<G> {
+    type Flaws = Flawless;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        self.goods.borrow_mut().push(good);
+    }
+}
+
+/// A [`FiniteQueue`] that retrieves the most recently stored good first.
+#[derive(Debug)]
+pub struct BoundedStack<G> {
+    /// The name of the stack.
+    name: String,
+    /// The goods in the order they were stored, with the most recent at the end.
+    goods: RefCell<Vec<G>>,
+    /// The maximum number of goods.
+    size: usize,
+}
+
+impl<G> Agent for BoundedStack<G> {
+    type Good = G;
+}
+
+impl<G> Consumer for BoundedStack<G> {
+    type Flaws = EmptyStock;
+
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        match self.goods.borrow_mut().pop() {
+            Some(good) => good,
+            None => throw!(self.failure(Fault::Insufficiency(EmptyStock))),
+        }
+    }
+}
+
+impl<G> Display for BoundedStack<G> {
+    /// Writes the name of the stack.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl<G> FiniteQueue<G> for BoundedStack<G> {
+    fn allocate<S>(name_str: &S, size: usize) -> Self
+    where
+        S: AsRef<str> + ?Sized,
+    {
+        Self {
+            name: name_str.as_ref().to_string(),
+            goods: RefCell::new(Vec::with_capacity(size)),
+            size,
+        }
+    }
+}
+
+impl<G> Producer for BoundedStack<G> {
+    type Flaws = FullStock;
+
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        let mut goods = self.goods.borrow_mut();
+
+        if goods.len() >= self.size {
+            throw!(self.recall(Fault::Insufficiency(FullStock), good));
+        }
+
+        goods.push(good);
+    }
+}
@@ -0,0 +1,165 @@
+//! Implements waiting on multiple [`Consumer`]s.
+use {
+    crate::{Agent, Consumer, Failure, Flaws, TryBlame},
+    core::{
+        convert::TryFrom,
+        sync::atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// Waits on several [`Consumer`]s and runs the arm of the first one that retrieves a good or fails due to a defect.
+///
+/// Each arm has the form `pattern = consumer => expression`, where `consumer` is any expression that evaluates to a [`Consumer`]. Each `consumer` is evaluated once. The [`Consumer`]s are polled in turn, blocking until one of them does not throw an insufficiency. The `pattern` of that arm is then matched against a [`Result`] containing either the retrieved good or the [`Failure`] caused by a defect, and the value of its `expression` is returned. A `break` or `continue` within an `expression` acts upon the loop enclosing the `select!`.
+///
+/// Each use of a `select!` starts polling at the arm after the one at which its previous use started, so that an arm with a steady supply of goods does not starve the others. After every arm has been polled, the current thread yields if the `std` feature is enabled.
+///
+/// The arm at which polling starts is tracked by a `static` counter that each `select!` invocation declares in its expansion. The counter is therefore global to the process: it is shared by every execution of that invocation on every thread, while separate invocations in the source have separate counters. When several threads run the same `select!` concurrently, each execution starts at the arm after the one of whichever execution started before it, so the rotation seen by a single thread may skip arms. Every execution still polls all arms before backing off.
+///
+/// The [`Consumer`]s may have different goods and [`Flaws`], but the expressions of all arms must have the same type.
+///
+/// # Examples
+///
+/// ```
+/// use market::{queue::{InfiniteQueue, UnboundedQueue}, select, Producer};
+///
+/// let numbers = UnboundedQueue::<u8>::allocate("numbers");
+/// let words = UnboundedQueue::<&str>::allocate("words");
+///
+/// words.produce("two").unwrap();
+///
+/// let description = select! {
+///     number = numbers => format!("number {:?}", number),
+///     word = words => format!("word {:?}", word.unwrap()),
+/// };
+///
+/// assert_eq!(description, "word \"two\"");
+/// ```
+#[macro_export]
+macro_rules! select {
+    (@bind [$($count:tt)*] [$($arm:tt)*]) => {
+        $crate::select!(@run [$($count)*] [$($arm)*])
+    };
+    (@bind [$($depth:tt)*] [$($arm:tt)*] $pattern:pat = $consumer:expr => $expression:expr $(, $($rest:tt)*)?) => {
+        // Each expansion creates a distinct `consumer` identifier, allowing each consumer to be bound once.
+        match &$consumer {
+            consumer => $crate::select!(@bind [$($depth)* _] [$($arm)* ([$($depth)*] $pattern, consumer, $expression)] $($($rest)*)?),
+        }
+    };
+    (@run [$($count:tt)*] [$(([$($depth:tt)*] $pattern:pat, $consumer:ident, $expression:expr))*]) => {{
+        // Shared by every execution of this invocation on every thread; see the documentation of select!.
+        static START: ::core::sync::atomic::AtomicUsize = ::core::sync::atomic::AtomicUsize::new(0);
+        let mut arms = $crate::__Arms::new(&START, 0 $(+ $crate::select!(@one $count))*);
+        // The outcome of arm n is wrapped in n `Err`s and an `Ok` so that arms with different outcomes are able to leave the loop.
+        let outcome = 'select: loop {
+            let arm = arms.advance();
+
+            $(
+                if arm == 0 $(+ $crate::select!(@one $depth))* {
+                    if let ::core::option::Option::Some(outcome) = $crate::__poll($consumer) {
+                        break 'select $crate::select!(@wrap [$($depth)*] outcome);
+                    }
+                }
+            )*
+        };
+
+        // Expressions are evaluated outside of the loop so that a `break` or `continue` within them acts upon the enclosing loop.
+        match outcome {
+            $(
+                $crate::select!(@unwrap [$($depth)*] ::core::result::Result::Ok($pattern)) => $expression,
+            )*
+            $crate::select!(@unwrap [$($count)*] never) => {
+                match ::core::convert::identity::<::core::convert::Infallible>(never) {}
+            }
+        }
+    }};
+    (@one $token:tt) => {
+        1
+    };
+    (@wrap [] $outcome:expr) => {
+        ::core::result::Result::Ok($outcome)
+    };
+    (@wrap [$first:tt $($depth:tt)*] $outcome:expr) => {
+        ::core::result::Result::Err($crate::select!(@wrap [$($depth)*] $outcome))
+    };
+    (@unwrap [] $pattern:pat) => {
+        $pattern
+    };
+    (@unwrap [$first:tt $($depth:tt)*] $pattern:pat) => {
+        ::core::result::Result::Err($crate::select!(@unwrap [$($depth)*] $pattern))
+    };
+    ($($arms:tt)+) => {
+        $crate::select!(@bind [] [] $($arms)+)
+    };
+}
+
+/// Determines the order in which the arms of a [`select!`] are polled.
+///
+/// Used by [`select!`].
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Arms {
+    /// The number of arms.
+    count: usize,
+    /// The index of the arm that is polled next.
+    next: usize,
+    /// The number of arms that are polled before backing off.
+    remaining: usize,
+}
+
+impl Arms {
+    /// Creates a new [`Arms`] of `count` arms, starting at the arm after the one at which the previous use of `start` started.
+    pub fn new(start: &AtomicUsize, count: usize) -> Self {
+        Self {
+            count,
+            next: start
+                .fetch_add(1, Ordering::Relaxed)
+                .checked_rem(count)
+                .unwrap_or(0),
+            remaining: count,
+        }
+    }
+
+    /// Returns the index of the arm to be polled, backing off once every arm has been polled.
+    pub fn advance(&mut self) -> usize {
+        if self.remaining == 0 {
+            backoff();
+            self.remaining = self.count;
+        }
+
+        let arm = self.next;
+
+        self.remaining = self.remaining.saturating_sub(1);
+        self.next = arm.saturating_add(1).checked_rem(self.count).unwrap_or(0);
+        arm
+    }
+}
+
+/// Waits before an agent that is spinning, such as the arms of a [`select!`], polls again.
+///
+/// Yields the current thread if `std` is available; otherwise signals that the processor is spinning.
+pub(crate) fn backoff() {
+    #[cfg(feature = "std")]
+    std::thread::yield_now();
+    #[cfg(not(feature = "std"))]
+    core::hint::spin_loop();
+}
+
+/// The outcome of a consumption from `C` that did not fail due to an insufficiency.
+type Outcome<C> = Result<<C as Agent>::Good, Failure<<<C as Consumer>::Flaws as Flaws>::Defect>>;
+
+/// Consumes from `consumer`, returning [`None`] if the consumption fails due to an insufficiency.
+///
+/// Used by [`select!`].
+#[doc(hidden)]
+pub fn poll<C>(consumer: &C) -> Option<Outcome<C>>
+where
+    C: Consumer,
+    <C::Flaws as Flaws>::Defect: Flaws<Defect = <C::Flaws as Flaws>::Defect>,
+    <<C::Flaws as Flaws>::Defect as Flaws>::Insufficiency:
+        TryFrom<<C::Flaws as Flaws>::Insufficiency>,
+{
+    match consumer.consume() {
+        Ok(good) => Some(Ok(good)),
+        Err(failure) => failure.try_blame().ok().map(Err),
+    }
+}
@@ -0,0 +1,9 @@
+//! Implements synchronization shared by agents that use a [`Mutex`].
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Locks `mutex`, recovering the guard if the lock is poisoned.
+///
+/// Agents only modify the data behind a [`Mutex`] with actions that cannot leave it in an invalid state, so a poisoned lock is safe to recover.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
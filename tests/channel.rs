@@ -0,0 +1,566 @@
+use {
+    market::{channel::*, *},
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    },
+};
+
+#[cfg(feature = "std")]
+#[test]
+fn infinite_produce_consume() {
+    let (producer, consumer) = StdInfiniteChannel::establish("channel");
+
+    assert_eq!(producer.produce(0), Ok(()));
+    assert_eq!(producer.produce(1), Ok(()));
+    assert_eq!(consumer.consume(), Ok(0));
+    assert_eq!(consumer.consume(), Ok(1));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn infinite_withdrawn_demand() {
+    let (producer, consumer) = StdInfiniteChannel::establish("channel");
+
+    drop(consumer);
+
+    assert_eq!(
+        producer.produce(0),
+        Err(producer.recall(Fault::Defect(WithdrawnDemand::default()), 0))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn infinite_withdrawn_supply() {
+    let (producer, consumer) = StdInfiniteChannel::establish("channel");
+    let clone = producer.clone();
+
+    assert_eq!(producer.produce(0), Ok(()));
+    drop(producer);
+    assert_eq!(clone.produce(1), Ok(()));
+    drop(clone);
+
+    assert_eq!(consumer.consume(), Ok(0));
+    assert_eq!(consumer.demand(), Ok(1));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Defect(WithdrawnSupply::default())))
+    );
+    assert_eq!(
+        consumer.demand(),
+        Err(consumer
+            .failure(Fault::Defect(WithdrawnSupply::default()))
+            .try_blame()
+            .unwrap())
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn infinite_demand_across_threads() {
+    let (producer, consumer) = StdInfiniteChannel::establish("channel");
+    let handle = thread::spawn(move || producer.produce_all(&mut (0..3)));
+
+    assert_eq!(consumer.demand(), Ok(0));
+    assert_eq!(consumer.demand(), Ok(1));
+    assert_eq!(consumer.demand(), Ok(2));
+    assert_eq!(handle.join().unwrap(), Ok(()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn finite_full_stock() {
+    let (producer, consumer) = StdFiniteChannel::establish("channel", 1);
+
+    assert_eq!(producer.produce(0), Ok(()));
+    assert_eq!(
+        producer.produce(1),
+        Err(producer.recall(Fault::Insufficiency(FullStock::default()), 1))
+    );
+    assert_eq!(consumer.consume(), Ok(0));
+    assert_eq!(producer.produce(1), Ok(()));
+    assert_eq!(consumer.consume(), Ok(1));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn finite_withdrawn_demand() {
+    let (producer, consumer) = StdFiniteChannel::establish("channel", 1);
+
+    drop(consumer);
+
+    assert_eq!(
+        producer.produce(0),
+        Err(producer.recall(Fault::Defect(WithdrawnDemand::default()), 0))
+    );
+    assert!(producer.force(0).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn finite_withdrawn_supply() {
+    let (producer, consumer) = StdFiniteChannel::establish("channel", 2);
+    let clone = producer.clone();
+
+    assert_eq!(producer.produce(0), Ok(()));
+    drop(producer);
+    assert_eq!(consumer.consume(), Ok(0));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+    drop(clone);
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Defect(WithdrawnSupply::default())))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn finite_force_demand_across_threads() {
+    let (producer, consumer) = StdFiniteChannel::establish("channel", 1);
+    let handle = thread::spawn(move || producer.force_all(&mut (0..10)));
+
+    for good in 0..10 {
+        assert_eq!(consumer.demand(), Ok(good));
+    }
+
+    assert_eq!(handle.join().unwrap(), Ok(()));
+    assert!(consumer.demand().is_err());
+}
+
+#[test]
+fn spsc_full_stock() {
+    let (producer, consumer) = SpscChannel::establish("channel", 2);
+
+    assert_eq!(producer.produce(0), Ok(()));
+    assert_eq!(producer.produce(1), Ok(()));
+    assert_eq!(
+        producer.produce(2),
+        Err(producer.recall(Fault::Insufficiency(FullStock::default()), 2))
+    );
+    assert_eq!(consumer.consume(), Ok(0));
+    assert_eq!(producer.produce(2), Ok(()));
+    assert_eq!(consumer.consume(), Ok(1));
+    assert_eq!(consumer.consume(), Ok(2));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn spsc_withdrawn_demand() {
+    let (producer, consumer) = SpscChannel::establish("channel", 1);
+
+    drop(consumer);
+
+    assert_eq!(
+        producer.produce(0),
+        Err(producer.recall(Fault::Defect(WithdrawnDemand::default()), 0))
+    );
+}
+
+#[test]
+fn spsc_withdrawn_supply() {
+    let (producer, consumer) = SpscChannel::establish("channel", 1);
+
+    assert_eq!(producer.produce(0), Ok(()));
+    drop(producer);
+
+    assert_eq!(consumer.consume(), Ok(0));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Defect(WithdrawnSupply::default())))
+    );
+}
+
+#[test]
+fn spsc_force_demand_across_threads() {
+    let (producer, consumer) = SpscChannel::establish("channel", 4);
+    let handle = thread::spawn(move || producer.force_all(&mut (0..100)));
+
+    for good in 0..100 {
+        assert_eq!(consumer.demand(), Ok(good));
+    }
+
+    assert_eq!(handle.join().unwrap(), Ok(()));
+    assert!(consumer.demand().is_err());
+}
+
+#[test]
+fn mpmc_full_stock() {
+    let (producer, consumer) = MpmcChannel::establish("channel", 3);
+
+    assert_eq!(producer.produce_all(&mut (0..3)), Ok(()));
+    assert_eq!(
+        producer.produce(3),
+        Err(producer.recall(Fault::Insufficiency(FullStock::default()), 3))
+    );
+
+    for good in 3..10 {
+        assert_eq!(consumer.consume(), Ok(good - 3));
+        assert_eq!(producer.produce(good), Ok(()));
+    }
+
+    assert_eq!(consumer.consume(), Ok(7));
+    assert_eq!(consumer.consume(), Ok(8));
+    assert_eq!(consumer.consume(), Ok(9));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn mpmc_withdrawn_demand_after_last_clone() {
+    let (producer, consumer) = MpmcChannel::establish("channel", 2);
+    let clone = consumer.clone();
+
+    drop(consumer);
+    assert_eq!(producer.produce(0), Ok(()));
+    drop(clone);
+    assert_eq!(
+        producer.produce(1),
+        Err(producer.recall(Fault::Defect(WithdrawnDemand::default()), 1))
+    );
+}
+
+#[test]
+fn mpmc_withdrawn_supply_after_last_clone() {
+    let (producer, consumer) = MpmcChannel::establish("channel", 2);
+    let clone = producer.clone();
+
+    drop(producer);
+    assert_eq!(clone.produce(0), Ok(()));
+    drop(clone);
+    assert_eq!(consumer.consume(), Ok(0));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Defect(WithdrawnSupply::default())))
+    );
+}
+
+#[test]
+fn mpmc_many_producers_and_consumers() {
+    let (producer, consumer) = MpmcChannel::establish("channel", 4);
+    let producers = (0..2)
+        .map(|offset| {
+            let producer = producer.clone();
+
+            thread::spawn(move || producer.force_all(&mut (0..50).map(|good| good * 2 + offset)))
+        })
+        .collect::<Vec<_>>();
+    let consumers = (0..2)
+        .map(|_| {
+            let consumer = consumer.clone();
+
+            thread::spawn(move || {
+                let mut goods = Vec::new();
+
+                while let Ok(good) = consumer.demand() {
+                    goods.push(good);
+                }
+
+                goods
+            })
+        })
+        .collect::<Vec<_>>();
+
+    drop(producer);
+    drop(consumer);
+
+    for handle in producers {
+        assert_eq!(handle.join().unwrap(), Ok(()));
+    }
+
+    let mut goods = consumers
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect::<Vec<_>>();
+
+    goods.sort_unstable();
+    assert_eq!(goods, (0..100).collect::<Vec<_>>());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn broadcast_every_consumer_receives_every_good() {
+    let (producer, consumer) = BroadcastChannel::establish("channel", 4);
+    let clone = consumer.clone();
+
+    assert_eq!(producer.produce_all(&mut (0..2)), Ok(()));
+
+    let subscriber = producer.subscribe();
+
+    assert_eq!(producer.produce(2), Ok(()));
+
+    for good in 0..3 {
+        assert_eq!(consumer.consume(), Ok(good));
+        assert_eq!(clone.consume(), Ok(good));
+    }
+
+    assert_eq!(subscriber.consume(), Ok(2));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn broadcast_lagged() {
+    let (producer, consumer) = BroadcastChannel::establish("channel", 2);
+
+    assert_eq!(producer.produce_all(&mut (0..5)), Ok(()));
+
+    let failure = consumer.consume().unwrap_err();
+
+    assert!(failure.is_defect());
+    assert_eq!(failure.to_string(), "channel: lagged behind by 3 goods");
+    assert_eq!(consumer.consume(), Ok(3));
+    assert_eq!(consumer.consume(), Ok(4));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn broadcast_withdrawn() {
+    let (producer, consumer) = BroadcastChannel::establish("channel", 2);
+
+    assert_eq!(producer.produce(0), Ok(()));
+    drop(producer);
+    assert_eq!(consumer.demand(), Ok(0));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Defect(WithdrawnSupply::default().into())))
+    );
+
+    let (producer, consumer) = BroadcastChannel::establish::<u8, _>("channel", 2);
+
+    drop(consumer);
+    assert_eq!(
+        producer.produce(0),
+        Err(producer.recall(Fault::Defect(WithdrawnDemand::default()), 0))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn oneshot_single_good() {
+    let (producer, consumer) = OneshotChannel::establish("channel");
+
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+    assert_eq!(producer.produce(0), Ok(()));
+    assert_eq!(
+        producer.produce(1),
+        Err(producer.recall(Fault::Defect(Spent::default().into()), 1))
+    );
+    assert_eq!(consumer.consume(), Ok(0));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Defect(WithdrawnSupply::default())))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn oneshot_withdrawn() {
+    let (producer, consumer) = OneshotChannel::establish::<u8, _>("channel");
+
+    drop(producer);
+    assert!(consumer.demand().is_err());
+
+    let (producer, consumer) = OneshotChannel::establish("channel");
+
+    drop(consumer);
+    assert_eq!(
+        producer.produce(0),
+        Err(producer.recall(Fault::Defect(WithdrawnDemand::default().into()), 0))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn oneshot_demand_across_threads() {
+    let (producer, consumer) = OneshotChannel::establish("channel");
+    let handle = thread::spawn(move || consumer.demand());
+
+    assert_eq!(producer.produce(0), Ok(()));
+    assert_eq!(handle.join().unwrap(), Ok(0));
+}
+
+/// Asserts that a rendezvous channel established by `C` only stores a good while a [`Consumer`] is waiting in [`Consumer::demand`].
+fn assert_rendezvous<C>()
+where
+    C: FiniteChannel<u8>,
+    C::Producer: Send + 'static,
+{
+    let (producer, consumer) = C::establish("channel", 0);
+
+    assert_eq!(
+        producer.produce(0),
+        Err(producer.recall(Fault::Insufficiency(FullStock::default()), 0))
+    );
+
+    let is_stored = Arc::new(AtomicBool::new(false));
+    let handle = {
+        let is_stored = Arc::clone(&is_stored);
+
+        thread::spawn(move || {
+            let outcome = producer.force(1);
+
+            is_stored.store(true, Ordering::SeqCst);
+            (producer, outcome)
+        })
+    };
+
+    // force blocks until a Consumer is waiting.
+    thread::sleep(Duration::from_millis(50));
+    assert!(!is_stored.load(Ordering::SeqCst));
+    assert_eq!(consumer.demand(), Ok(1));
+
+    let (producer, outcome) = handle.join().unwrap();
+
+    assert_eq!(outcome, Ok(()));
+    assert!(is_stored.load(Ordering::SeqCst));
+    assert_eq!(
+        producer.produce(2),
+        Err(producer.recall(Fault::Insufficiency(FullStock::default()), 2))
+    );
+
+    let handle = thread::spawn(move || producer.force_all(&mut (0..3)));
+
+    for good in 0..3 {
+        assert_eq!(consumer.demand(), Ok(good));
+    }
+
+    assert_eq!(handle.join().unwrap(), Ok(()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn finite_rendezvous() {
+    assert_rendezvous::<StdFiniteChannel>();
+}
+
+#[test]
+fn spsc_rendezvous() {
+    assert_rendezvous::<SpscChannel>();
+}
+
+#[test]
+fn mpmc_rendezvous() {
+    assert_rendezvous::<MpmcChannel>();
+}
+
+#[test]
+fn mpmc_rendezvous_stores_one_good_per_demand() {
+    for _ in 0..50 {
+        let (producer, consumer) = MpmcChannel::establish("channel", 0);
+        let is_done = Arc::new(AtomicBool::new(false));
+        let handles = (0..2)
+            .map(|_| {
+                let producer = producer.clone();
+                let is_done = Arc::clone(&is_done);
+
+                thread::spawn(move || {
+                    let mut stored = 0;
+
+                    while !is_done.load(Ordering::SeqCst) {
+                        if producer.produce(0).is_ok() {
+                            stored += 1;
+                        }
+                    }
+
+                    stored
+                })
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(consumer.demand(), Ok(0));
+        thread::sleep(Duration::from_millis(5));
+        is_done.store(true, Ordering::SeqCst);
+        assert_eq!(
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .sum::<u32>(),
+            1
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn watch_latest_good() {
+    let (producer, consumer) = WatchChannel::establish("channel");
+
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+    assert_eq!(producer.produce_all(&mut (0..3)), Ok(()));
+
+    let subscriber = producer.subscribe();
+
+    assert_eq!(consumer.consume(), Ok(2));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+    assert_eq!(subscriber.consume(), Ok(2));
+    assert_eq!(producer.produce(3), Ok(()));
+    assert_eq!(consumer.consume(), Ok(3));
+    assert_eq!(subscriber.consume(), Ok(3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn watch_withdrawn() {
+    let (producer, consumer) = WatchChannel::establish("channel");
+
+    assert_eq!(producer.produce(0), Ok(()));
+    drop(producer);
+    assert_eq!(consumer.demand(), Ok(0));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Defect(WithdrawnSupply::default())))
+    );
+
+    let (producer, consumer) = WatchChannel::establish("channel");
+
+    drop(consumer);
+    assert_eq!(
+        producer.produce(0),
+        Err(producer.recall(Fault::Defect(WithdrawnDemand::default()), 0))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn finite_drop_oldest() {
+    let (producer, consumer) =
+        StdFiniteChannel::establish_with_overflow("channel", 2, queue::Overflow::DropOldest);
+
+    assert_eq!(producer.produce_all(&mut (0..3)), Ok(()));
+    assert_eq!(producer.force(3), Ok(()));
+    assert_eq!(consumer.consume(), Ok(2));
+    assert_eq!(consumer.consume(), Ok(3));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
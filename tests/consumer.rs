@@ -2,8 +2,9 @@ use {
     core::{
         cell::RefCell,
         fmt::{self, Display, Formatter},
+        num::NonZeroUsize,
     },
-    fehler::throws,
+    fehler::{throw, throws},
     market::*,
     never::Never,
     std::collections::VecDeque,
@@ -44,6 +45,34 @@ impl Display for MockConsumer {
     }
 }
 
+/// Only retrieves its good by blocking.
+struct MockBlockingConsumer;
+
+impl Agent for MockBlockingConsumer {
+    type Good = u8;
+}
+
+impl Consumer for MockBlockingConsumer {
+    type Flaws = EmptyStock;
+
+    #[allow(unreachable_code)] // Issue with fehler (#53) which has been resolved but not released.
+    #[throws(Failure<Self::Flaws>)]
+    fn consume(&self) -> Self::Good {
+        throw!(self.failure(Fault::Insufficiency(EmptyStock::default())))
+    }
+
+    #[throws(Failure<Flawless>)]
+    fn demand(&self) -> Self::Good {
+        1
+    }
+}
+
+impl Display for MockBlockingConsumer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "MockBlockingConsumer")
+    }
+}
+
 #[derive(Debug, PartialEq)]
 struct MockMisstep;
 
@@ -88,3 +117,353 @@ fn demand_fault() {
     );
     assert_eq!(consumer.demand(), Ok(0));
 }
+
+#[test]
+fn goods_until_insufficiency() {
+    let consumer = MockConsumer::new(vec![
+        Ok(0),
+        Ok(1),
+        Err(Fault::Insufficiency(EmptyStock::default())),
+        Ok(2),
+    ]);
+
+    assert_eq!(consumer.goods().collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(consumer.goods().collect::<Vec<_>>(), vec![2]);
+}
+
+#[test]
+fn goods_until_defect() {
+    let consumer = MockConsumer::new(vec![Ok(0), Err(Fault::Defect(MockDefect)), Ok(1)]);
+    let mut goods = consumer.goods();
+
+    assert_eq!(goods.by_ref().collect::<Vec<_>>(), vec![0]);
+    assert_eq!(goods.next(), None);
+    assert_eq!(
+        goods.into_failure(),
+        Some(consumer.failure(Fault::Defect(MockDefect)))
+    );
+}
+
+#[test]
+fn demands_until_defect() {
+    let consumer = MockConsumer::new(vec![
+        Ok(0),
+        Err(Fault::Insufficiency(EmptyStock::default())),
+        Ok(1),
+        Err(Fault::Defect(MockDefect)),
+        Ok(2),
+    ]);
+    let mut demands = consumer.demands();
+
+    assert_eq!(demands.by_ref().collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(demands.next(), None);
+    assert_eq!(
+        demands.into_failure(),
+        Some(
+            consumer
+                .failure(Fault::Defect(MockDefect))
+                .try_blame()
+                .unwrap()
+        )
+    );
+}
+
+#[test]
+fn consumer_fn_failure_names_agent() {
+    let goods = RefCell::new(vec![0]);
+    let consumer = consumer_fn("source", || {
+        goods
+            .borrow_mut()
+            .pop()
+            .ok_or(Fault::<EmptyStock>::Insufficiency(EmptyStock::default()))
+    });
+
+    assert_eq!(consumer.consume(), Ok(0));
+    assert_eq!(
+        consumer.consume().map_err(|failure| failure.to_string()),
+        Err("source: insufficient goods".to_string())
+    );
+}
+
+#[test]
+fn map_goods() {
+    let consumer = MockConsumer::new(vec![Ok(0), Ok(1)]).map(|good| good * 2);
+
+    assert_eq!(consumer.consume(), Ok(0));
+    assert_eq!(consumer.consume(), Ok(2));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn adaptors_forward_demand() {
+    assert_eq!(MockBlockingConsumer.map(|good| good * 2).demand(), Ok(2));
+    assert_eq!(MockBlockingConsumer.filter(|_| true).demand(), Ok(1));
+    assert_eq!(MockBlockingConsumer.filter_map(Some).demand(), Ok(1));
+    assert_eq!(MockBlockingConsumer.inspect(|_| {}).demand(), Ok(1));
+}
+
+#[test]
+fn filter_empty_when_all_rejected() {
+    let consumer = MockConsumer::new(vec![
+        Ok(1),
+        Ok(2),
+        Ok(3),
+        Err(Fault::Insufficiency(EmptyStock::default())),
+        Ok(5),
+        Ok(6),
+    ])
+    .filter(|good| good % 2 == 0);
+
+    assert_eq!(consumer.consume(), Ok(2));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+    assert_eq!(consumer.consume(), Ok(6));
+    assert_eq!(consumer.to_string(), "MockConsumer");
+}
+
+#[test]
+fn filter_map_passes_defect() {
+    let consumer = MockConsumer::new(vec![Ok(1), Err(Fault::Defect(MockDefect))])
+        .filter_map(|good| good.checked_sub(2));
+
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Defect(MockDefect)))
+    );
+}
+
+#[test]
+fn inspect_goods() {
+    let inspected = RefCell::new(Vec::new());
+    let consumer =
+        MockConsumer::new(vec![Ok(0), Ok(1)]).inspect(|good| inspected.borrow_mut().push(*good));
+
+    assert_eq!(consumer.goods().collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(*inspected.borrow(), vec![0, 1]);
+}
+
+#[test]
+fn merge_round_robin() {
+    let merge = Merge::new(
+        "merge",
+        vec![
+            MockConsumer::new(vec![Ok(0), Ok(1)]),
+            MockConsumer::new(vec![Ok(2), Ok(3), Ok(4)]),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(merge.goods().collect::<Vec<_>>(), vec![0, 2, 1, 3, 4]);
+    assert_eq!(
+        merge.consume(),
+        Err(merge.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn merge_weighted() {
+    let merge = Merge::weighted(
+        "merge",
+        vec![
+            (
+                MockConsumer::new(vec![Ok(0), Ok(1), Ok(2)]),
+                NonZeroUsize::new(2).unwrap(),
+            ),
+            (
+                MockConsumer::new(vec![Ok(3), Ok(4)]),
+                NonZeroUsize::new(1).unwrap(),
+            ),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(merge.goods().collect::<Vec<_>>(), vec![0, 1, 3, 2, 4]);
+}
+
+#[test]
+fn merge_requires_sources() {
+    assert_eq!(
+        Merge::<MockConsumer>::new("merge", Vec::new()).err(),
+        Some(NoSources::default())
+    );
+}
+
+#[test]
+fn merge_defect_when_all_withdrawn() {
+    let merge = Merge::new(
+        "merge",
+        vec![
+            MockConsumer::new(vec![
+                Err(Fault::Defect(MockDefect)),
+                Err(Fault::Defect(MockDefect)),
+            ]),
+            MockConsumer::new(vec![Ok(0), Err(Fault::Defect(MockDefect))]),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(merge.consume(), Ok(0));
+    assert_eq!(
+        merge.consume(),
+        Err(merge.failure(Fault::Defect(MockDefect)))
+    );
+
+    let merge = Merge::new(
+        "merge",
+        vec![
+            MockConsumer::new(vec![Err(Fault::Defect(MockDefect))]),
+            MockConsumer::new(vec![Ok(0)]),
+        ],
+    )
+    .unwrap()
+    .fail_fast();
+
+    assert_eq!(
+        merge.consume(),
+        Err(merge.failure(Fault::Defect(MockDefect)))
+    );
+
+    let merge = Merge::new(
+        "merge",
+        vec![
+            MockConsumer::new(vec![Err(Fault::Insufficiency(EmptyStock::default()))]),
+            MockConsumer::new(vec![Err(Fault::Defect(MockDefect))]),
+        ],
+    )
+    .unwrap()
+    .fail_fast();
+
+    assert_eq!(
+        merge.consume(),
+        Err(merge.failure(Fault::Defect(MockDefect)))
+    );
+}
+
+#[test]
+fn select_first_available() {
+    let numbers = MockConsumer::new(vec![
+        Err(Fault::Insufficiency(EmptyStock::default())),
+        Ok(1),
+    ]);
+    let words = IterConsumer::new("words", vec!["one"]).withdraw_when_exhausted();
+    let select = || {
+        select! {
+            number = numbers => number.map(u32::from).map_err(|_| "numbers"),
+            word = words => word.map(|w| w.len() as u32).map_err(|_| "words"),
+        }
+    };
+
+    assert_eq!(select(), Ok(3));
+    // Each use starts polling at the next arm.
+    assert_eq!(select(), Err("words"));
+    assert_eq!(select(), Ok(1));
+}
+
+#[test]
+fn select_rotates_first_arm() {
+    let evens = IterConsumer::new("evens", vec![0, 2]);
+    let odds = IterConsumer::new("odds", vec![1, 3]);
+    let mut selected = Vec::new();
+
+    for _ in 0..4 {
+        selected.push(select! {
+            even = evens => even.unwrap(),
+            odd = odds => odd.unwrap(),
+        });
+    }
+
+    assert_eq!(selected, vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn select_break_exits_enclosing_loop() {
+    let words = IterConsumer::new("words", vec!["one", "two"]).withdraw_when_exhausted();
+    let mut selected = Vec::new();
+
+    loop {
+        select! {
+            word = words => match word {
+                Ok(word) => selected.push(word),
+                Err(_) => break,
+            },
+        }
+    }
+
+    assert_eq!(selected, vec!["one", "two"]);
+}
+
+#[test]
+fn batched_available_goods() {
+    let consumer = MockConsumer::new(vec![
+        Ok(0),
+        Ok(1),
+        Ok(2),
+        Err(Fault::Insufficiency(EmptyStock::default())),
+        Ok(3),
+        Err(Fault::Defect(MockDefect)),
+    ])
+    .batched(NonZeroUsize::new(2).unwrap());
+
+    assert_eq!(consumer.consume(), Ok(vec![0, 1]));
+    assert_eq!(consumer.consume(), Ok(vec![2]));
+    assert_eq!(consumer.consume(), Ok(vec![3]));
+    // The defect thrown after retrieving 3 is thrown by the next consumption.
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Defect(MockDefect)))
+    );
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn batched_timeout_partial_batch() {
+    use std::{thread, time::Duration};
+
+    let consumer = MockConsumer::new(vec![
+        Ok(0),
+        Err(Fault::Insufficiency(EmptyStock::default())),
+        Ok(1),
+        Ok(2),
+        Ok(3),
+        Err(Fault::Insufficiency(EmptyStock::default())),
+        Err(Fault::Insufficiency(EmptyStock::default())),
+        Ok(4),
+        Err(Fault::Defect(MockDefect)),
+    ])
+    .batched_timeout(NonZeroUsize::new(3).unwrap(), Duration::from_millis(20));
+
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+    assert_eq!(consumer.consume(), Ok(vec![0, 1, 2]));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+    thread::sleep(Duration::from_millis(20));
+    assert_eq!(consumer.consume(), Ok(vec![3]));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+    // A defect retrieves the partial batch before the timeout and is thrown by the next consumption.
+    assert_eq!(consumer.consume(), Ok(vec![4]));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Defect(MockDefect)))
+    );
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
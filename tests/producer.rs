@@ -1,6 +1,6 @@
 use {
     core::{
-        cell::RefCell,
+        cell::{Cell, RefCell},
         fmt::{self, Debug, Display, Formatter},
     },
     fehler::{throw, throws},
@@ -8,6 +8,7 @@ use {
     never::Never,
     std::{
         collections::VecDeque,
+        rc::Rc,
         sync::atomic::{AtomicU8, Ordering},
     },
 };
@@ -92,6 +93,32 @@ impl Producer for U8Producer {
     }
 }
 
+/// Only stores goods by blocking.
+struct MockBlockingProducer;
+
+impl Agent for MockBlockingProducer {
+    type Good = u8;
+}
+
+impl Display for MockBlockingProducer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "MockBlockingProducer")
+    }
+}
+
+impl Producer for MockBlockingProducer {
+    type Flaws = FullStock;
+
+    #[allow(unreachable_code)] // Issue with fehler (#53) which has been resolved but not released.
+    #[throws(Recall<Self::Flaws, Self::Good>)]
+    fn produce(&self, good: Self::Good) {
+        throw!(self.recall(Fault::Insufficiency(FullStock::default()), good))
+    }
+
+    #[throws(Recall<Flawless, Self::Good>)]
+    fn force(&self, _good: Self::Good) {}
+}
+
 #[test]
 fn produce_goods_success() {
     let producer = U8Producer::default();
@@ -205,3 +232,276 @@ fn force_goods_fault_middle() {
     );
     assert_eq!(producer.goods, RefCell::new(vec![0]));
 }
+
+#[test]
+fn extend_from_iter() {
+    let consumer = IterConsumer::new("consumer", vec![0, 1, 2]);
+    let producer = ExtendProducer::new("producer", Vec::new());
+
+    assert_eq!(producer.produce_goods(&consumer), Ok(()));
+    assert_eq!(
+        consumer.consume(),
+        Err(consumer.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+    assert_eq!(producer.into_inner(), vec![0, 1, 2]);
+}
+
+#[test]
+fn extend_until_withdrawn() {
+    let consumer = IterConsumer::new("consumer", "abc".chars()).withdraw_when_exhausted();
+    let producer = ExtendProducer::new("producer", String::new());
+
+    assert_eq!(
+        producer.force_goods(&consumer),
+        Err(Blockage::Consumption(
+            consumer
+                .failure(Fault::Defect(channel::WithdrawnSupply::default()))
+                .try_blame()
+                .unwrap()
+        ))
+    );
+    assert_eq!(producer.into_inner(), "abc");
+}
+
+#[test]
+fn producer_fn_recalls_good() {
+    let goods = RefCell::new(Vec::new());
+    let producer = producer_fn("sink", |good: u8| {
+        if good < 2 {
+            goods.borrow_mut().push(good);
+            Ok(())
+        } else {
+            Err((
+                Fault::<ProductionFlaws<MockDefect>>::Defect(MockDefect),
+                good,
+            ))
+        }
+    });
+
+    assert_eq!(
+        producer.produce_all(&mut (0..3)),
+        Err(producer.recall(Fault::Defect(MockDefect), 2))
+    );
+    assert_eq!(producer.to_string(), "sink");
+    assert_eq!(*goods.borrow(), vec![0, 1]);
+}
+
+#[test]
+fn map_input_recalls_original_good() {
+    let mut producer = U8Producer::default();
+
+    producer.fail_on_produce_call(1, Fault::Insufficiency(FullStock::default()));
+
+    let producer = producer.map_input(|good: &char| *good as u8);
+
+    assert_eq!(producer.produce('a'), Ok(()));
+
+    let recall = producer.produce('b').unwrap_err();
+
+    assert_eq!(
+        recall,
+        producer.recall(Fault::Insufficiency(FullStock::default()), 'b')
+    );
+    assert_eq!(recall.into_good(), 'b');
+}
+
+#[test]
+fn map_input_forwards_force() {
+    let producer = MockBlockingProducer.map_input(|good: &char| *good as u8);
+
+    assert_eq!(producer.force('a'), Ok(()));
+}
+
+/// Returns a boxed [`Producer`] that stores goods into `goods` after throwing [`FullStock`] `fails` times.
+fn recorder(
+    goods: &Rc<RefCell<Vec<u8>>>,
+    fails: u8,
+) -> Box<dyn Producer<Good = u8, Flaws = FullStock>> {
+    let goods = Rc::clone(goods);
+    let fails = Cell::new(fails);
+
+    Box::new(producer_fn("recorder", move |good: u8| {
+        match fails.get().checked_sub(1) {
+            Some(remaining) => {
+                fails.set(remaining);
+                Err((Fault::Insufficiency(FullStock::default()), good))
+            }
+            None => {
+                goods.borrow_mut().push(good);
+                Ok(())
+            }
+        }
+    }))
+}
+
+#[test]
+fn tee_stores_into_all_branches() {
+    let live = Rc::new(RefCell::new(Vec::new()));
+    let audit = Rc::new(RefCell::new(Vec::new()));
+    let tee = Tee::new("tee", vec![recorder(&live, 0), recorder(&audit, 0)]);
+
+    assert_eq!(tee.produce_all(&mut (0..2)), Ok(()));
+    assert_eq!(*live.borrow(), vec![0, 1]);
+    assert_eq!(*audit.borrow(), vec![0, 1]);
+    assert_eq!(tee.to_string(), "tee");
+}
+
+#[test]
+fn tee_stores_new_good_after_insufficiency() {
+    let live = Rc::new(RefCell::new(Vec::new()));
+    let audit = Rc::new(RefCell::new(Vec::new()));
+    let tee = Tee::new("tee", vec![recorder(&live, 0), recorder(&audit, 1)]);
+
+    assert_eq!(
+        tee.produce(0),
+        Err(tee.recall(
+            Fault::Insufficiency(Branch::new(1, FullStock::default())),
+            0
+        ))
+    );
+    // A new good is stored into every branch.
+    assert_eq!(tee.produce(1), Ok(()));
+    // A recalled good is only stored into the branches that have not stored it.
+    assert_eq!(tee.produce_from(1, 0), Ok(()));
+    assert_eq!(*live.borrow(), vec![0, 1]);
+    assert_eq!(*audit.borrow(), vec![1, 0]);
+}
+
+#[test]
+fn tee_force_stores_once_into_each_branch() {
+    let live = Rc::new(RefCell::new(Vec::new()));
+    let audit = Rc::new(RefCell::new(Vec::new()));
+    let tee = Tee::new("tee", vec![recorder(&live, 0), recorder(&audit, 2)]);
+
+    assert_eq!(tee.force(0), Ok(()));
+    assert_eq!(*live.borrow(), vec![0]);
+    assert_eq!(*audit.borrow(), vec![0]);
+}
+
+#[test]
+fn tee_identifies_branch() {
+    let mut audit = U8Producer::default();
+
+    audit.fail_on_produce_call(0, Fault::Insufficiency(FullStock::default()));
+
+    let tee = Tee::new("tee", vec![U8Producer::default(), audit]);
+
+    assert_eq!(
+        tee.produce(0),
+        Err(tee.recall(
+            Fault::Insufficiency(Branch::new(1, FullStock::default())),
+            0
+        ))
+    );
+    assert_eq!(tee.produce_from(1, 0), Ok(()));
+    assert_eq!(tee.produce(1), Ok(()));
+
+    let mut sink = U8Producer::default();
+
+    sink.fail_on_produce_call(0, Fault::Defect(MockDefect));
+
+    let tee = Tee::new("tee", vec![sink]);
+
+    assert_eq!(
+        tee.produce(0),
+        Err(tee.recall(Fault::Defect(Branch::new(0, MockDefect)), 0))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn batching_flushes_on_size_and_time() {
+    use {
+        core::num::NonZeroUsize,
+        std::{thread, time::Duration},
+    };
+
+    let batches = RefCell::new(Vec::new());
+    let producer = BatchingProducer::new(
+        producer_fn("batches", |batch: Vec<u8>| {
+            batches.borrow_mut().push(batch);
+            Ok::<_, (Fault<Flawless>, _)>(())
+        }),
+        NonZeroUsize::new(2).unwrap(),
+        Duration::from_millis(20),
+    );
+
+    assert_eq!(producer.produce_all(&mut (0..3)), Ok(()));
+    assert_eq!(producer.flush_if_due(), Ok(()));
+    thread::sleep(Duration::from_millis(20));
+    assert_eq!(producer.flush_if_due(), Ok(()));
+    assert_eq!(producer.produce(3), Ok(()));
+    thread::sleep(Duration::from_millis(20));
+    assert_eq!(producer.produce(4), Ok(()));
+    assert_eq!(producer.produce(5), Ok(()));
+    assert_eq!(producer.flush(), Ok(()));
+    assert_eq!(
+        *batches.borrow(),
+        vec![vec![0, 1], vec![2], vec![3, 4], vec![5]]
+    );
+    assert_eq!(producer.to_string(), "batches");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn batching_recalls_unflushed_batch() {
+    use {core::num::NonZeroUsize, std::time::Duration};
+
+    let batches = RefCell::new(Vec::new());
+    let producer = BatchingProducer::new(
+        producer_fn("batches", |batch: Vec<u8>| {
+            if batch.contains(&2) {
+                Err((
+                    Fault::<ProductionFlaws<MockDefect>>::Insufficiency(FullStock::default()),
+                    batch,
+                ))
+            } else {
+                batches.borrow_mut().push(batch);
+                Ok(())
+            }
+        }),
+        NonZeroUsize::new(3).unwrap(),
+        Duration::from_secs(60),
+    );
+
+    assert_eq!(producer.produce(1), Ok(()));
+    assert_eq!(producer.produce(2), Ok(()));
+    // The good that triggered the failed flush is recalled and the rest stays buffered.
+    assert_eq!(
+        producer.produce(3),
+        Err(producer.recall(Fault::Insufficiency(FullStock::default()), 3))
+    );
+    assert_eq!(producer.flush().map_err(Recall::into_good), Err(vec![1, 2]));
+    assert_eq!(producer.flush(), Ok(()));
+    assert_eq!(producer.produce_all(&mut (3..6)), Ok(()));
+    assert_eq!(*batches.borrow(), vec![vec![3, 4, 5]]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn batching_throws_failure_of_kept_batch() {
+    use {core::num::NonZeroUsize, std::time::Duration};
+
+    let producer = BatchingProducer::new(
+        producer_fn("batches", |_: Vec<u8>| {
+            Err((
+                Fault::<FullStock>::Insufficiency(FullStock::default()),
+                Vec::new(),
+            ))
+        }),
+        NonZeroUsize::new(1).unwrap(),
+        Duration::from_secs(60),
+    );
+
+    // The wrapped producer kept the batch, so the failure is thrown by the next production.
+    assert_eq!(producer.produce(0), Ok(()));
+    assert_eq!(
+        producer.produce(1),
+        Err(producer.recall(Fault::Insufficiency(FullStock::default()), 1))
+    );
+    assert_eq!(producer.flush(), Ok(()));
+    // Or by the next flush.
+    assert_eq!(producer.produce(2), Ok(()));
+    assert_eq!(producer.flush().map_err(Recall::into_good), Err(Vec::new()));
+    assert_eq!(producer.flush(), Ok(()));
+}
@@ -0,0 +1,366 @@
+use market::{queue::*, *};
+
+#[test]
+fn unbounded_first_in_first_out() {
+    let queue = UnboundedQueue::allocate("queue");
+
+    assert_eq!(queue.produce_all(&mut (0..3)), Ok(()));
+    assert_eq!(queue.consume(), Ok(0));
+    assert_eq!(queue.consume(), Ok(1));
+    assert_eq!(queue.consume(), Ok(2));
+    assert_eq!(
+        queue.consume(),
+        Err(queue.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn bounded_full_stock() {
+    let queue = BoundedQueue::allocate("queue", 2);
+
+    assert_eq!(queue.produce(0), Ok(()));
+    assert_eq!(queue.produce(1), Ok(()));
+    assert_eq!(
+        queue.produce(2),
+        Err(queue.recall(Fault::Insufficiency(FullStock::default()), 2))
+    );
+    assert_eq!(queue.consume(), Ok(0));
+    assert_eq!(queue.produce(2), Ok(()));
+    assert_eq!(queue.consume(), Ok(1));
+    assert_eq!(queue.consume(), Ok(2));
+    assert_eq!(
+        queue.consume(),
+        Err(queue.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn bounded_produce_goods() {
+    let source = UnboundedQueue::allocate("source");
+    let queue = BoundedQueue::allocate("queue", 2);
+
+    assert_eq!(source.produce_all(&mut (0..3)), Ok(()));
+    assert_eq!(
+        queue.produce_goods(&source),
+        Err(Blockage::Production(
+            queue.recall(Fault::Insufficiency(FullStock::default()), 2)
+        ))
+    );
+    assert_eq!(queue.consume(), Ok(0));
+    assert_eq!(queue.consume(), Ok(1));
+}
+
+#[test]
+fn ring_wraps_around() {
+    let queue = RingQueue::<u8, 2>::new("ring");
+
+    assert_eq!(queue.produce(0), Ok(()));
+    assert_eq!(queue.produce(1), Ok(()));
+    assert_eq!(
+        queue.produce(2),
+        Err(queue.recall(Fault::Insufficiency(FullStock::default()), 2))
+    );
+    assert_eq!(queue.consume(), Ok(0));
+    assert_eq!(queue.produce(2), Ok(()));
+    assert_eq!(queue.consume(), Ok(1));
+    assert_eq!(queue.consume(), Ok(2));
+    assert_eq!(
+        queue.consume(),
+        Err(queue.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn ring_without_slots() {
+    let queue = RingQueue::<u8, 0>::new("ring");
+
+    assert_eq!(
+        queue.produce(0),
+        Err(queue.recall(Fault::Insufficiency(FullStock::default()), 0))
+    );
+    assert_eq!(
+        queue.consume(),
+        Err(queue.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn priority_greatest_first() {
+    let queue = PriorityQueue::allocate("queue");
+
+    assert_eq!(queue.produce_all(&mut vec![1, 3, 2].into_iter()), Ok(()));
+    assert_eq!(queue.consume(), Ok(3));
+    assert_eq!(queue.consume(), Ok(2));
+    assert_eq!(queue.consume(), Ok(1));
+    assert_eq!(
+        queue.consume(),
+        Err(queue.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn priority_by_key_ties_first_in_first_out() {
+    let queue = PriorityQueue::by_key("queue", |&(priority, _): &(u8, char)| priority);
+
+    assert_eq!(
+        queue.produce_all(&mut vec![(0, 'a'), (1, 'b'), (0, 'c'), (1, 'd')].into_iter()),
+        Ok(())
+    );
+    assert_eq!(queue.consume(), Ok((1, 'b')));
+    assert_eq!(queue.consume(), Ok((1, 'd')));
+    assert_eq!(queue.consume(), Ok((0, 'a')));
+    assert_eq!(queue.consume(), Ok((0, 'c')));
+}
+
+#[test]
+fn priority_borrowed_comparator_is_send() {
+    fn assert_send<T: Send>(_: &T) {}
+
+    let weights = [3, 1, 4, 1, 5, 9, 2, 6];
+    let queue = BoundedPriorityQueue::by_key("queue", 8, |good: &usize| weights[*good]);
+
+    assert_send(&queue);
+    assert_eq!(queue.produce_all(&mut (0..8)), Ok(()));
+    assert_eq!(
+        queue.goods().collect::<Vec<_>>(),
+        vec![5, 7, 4, 2, 0, 6, 1, 3]
+    );
+}
+
+#[test]
+fn bounded_priority_full_stock() {
+    let queue = BoundedPriorityQueue::by("queue", 2, |a: &u8, b: &u8| b.cmp(a));
+
+    assert_eq!(queue.produce(2), Ok(()));
+    assert_eq!(queue.produce(1), Ok(()));
+    assert_eq!(
+        queue.produce(0),
+        Err(queue.recall(Fault::Insufficiency(FullStock::default()), 0))
+    );
+    assert_eq!(queue.consume(), Ok(1));
+    assert_eq!(queue.consume(), Ok(2));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn delay_holds_until_deadline() {
+    use std::time::{Duration, Instant};
+
+    let queue = DelayQueue::allocate("queue");
+    let start = Instant::now();
+
+    assert_eq!(queue.produce_after(Duration::from_millis(50), 1), Ok(()));
+    queue.produce_at(start, 0);
+    assert_eq!(queue.consume(), Ok(0));
+    assert_eq!(
+        queue.consume(),
+        Err(queue.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+    assert_eq!(queue.demand(), Ok(1));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn delay_rejects_unrepresentable_deadline() {
+    use std::time::Duration;
+
+    let queue = DelayQueue::allocate("queue");
+    let recall = queue.produce_after(Duration::MAX, 0).unwrap_err();
+
+    assert_eq!(
+        recall.to_string(),
+        "`queue: deadline is out of range` caused recall of good 0"
+    );
+    assert_eq!(recall.into_good(), 0);
+    assert_eq!(
+        queue.consume(),
+        Err(queue.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn delay_demand_wakes_on_produce() {
+    use std::{sync::Arc, thread};
+
+    let queue = Arc::new(DelayQueue::allocate("queue"));
+    let producer = Arc::clone(&queue);
+    let handle = thread::spawn(move || producer.produce(0));
+
+    assert_eq!(queue.demand(), Ok(0));
+    assert_eq!(handle.join().unwrap(), Ok(()));
+}
+
+#[test]
+fn stack_last_in_first_out() {
+    let stack = Stack::allocate("stack");
+
+    assert_eq!(stack.produce_all(&mut (0..3)), Ok(()));
+    assert_eq!(stack.consume(), Ok(2));
+    assert_eq!(stack.consume(), Ok(1));
+    assert_eq!(stack.consume(), Ok(0));
+    assert_eq!(
+        stack.consume(),
+        Err(stack.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn bounded_stack_full_stock() {
+    let stack = BoundedStack::allocate("stack", 2);
+
+    assert_eq!(stack.produce(0), Ok(()));
+    assert_eq!(stack.produce(1), Ok(()));
+    assert_eq!(
+        stack.produce(2),
+        Err(stack.recall(Fault::Insufficiency(FullStock::default()), 2))
+    );
+    assert_eq!(stack.consume(), Ok(1));
+    assert_eq!(stack.produce(2), Ok(()));
+    assert_eq!(stack.consume(), Ok(2));
+    assert_eq!(stack.consume(), Ok(0));
+}
+
+#[test]
+fn bounded_drop_newest() {
+    let queue = BoundedQueue::with_overflow("queue", 2, Overflow::DropNewest);
+
+    assert_eq!(queue.produce_all(&mut (0..4)), Ok(()));
+    assert_eq!(queue.consume(), Ok(0));
+    assert_eq!(queue.consume(), Ok(1));
+    assert_eq!(
+        queue.consume(),
+        Err(queue.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[test]
+fn bounded_drop_oldest_evicts() {
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    let evicted = Arc::new(Mutex::new(Vec::new()));
+    let eviction = Arc::clone(&evicted);
+    let queue = BoundedQueue::with_overflow("queue", 2, Overflow::DropOldest)
+        .evict_with(move |good| eviction.lock().unwrap().push(good));
+
+    // The queue is able to be moved to another thread along with its callback.
+    let queue = thread::spawn(move || {
+        assert_eq!(queue.produce_all(&mut (0..4)), Ok(()));
+        queue
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(*evicted.lock().unwrap(), vec![0, 1]);
+    assert_eq!(queue.consume(), Ok(2));
+    assert_eq!(queue.consume(), Ok(3));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dedup_ignores_goods_in_stock() {
+    let queue = DedupQueue::new(UnboundedQueue::allocate("queue"));
+
+    assert_eq!(
+        queue.produce_all(&mut vec![0, 1, 0, 1, 2].into_iter()),
+        Ok(())
+    );
+    assert_eq!(queue.consume(), Ok(0));
+    assert_eq!(queue.produce(0), Ok(()));
+    assert_eq!(queue.consume(), Ok(1));
+    assert_eq!(queue.consume(), Ok(2));
+    assert_eq!(queue.consume(), Ok(0));
+    assert_eq!(
+        queue.consume(),
+        Err(queue.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dedup_rejects_duplicate_keys() {
+    let queue = DedupQueue::by_key(
+        BoundedQueue::allocate("queue", 2),
+        |&(key, _): &(u8, char)| key,
+    )
+    .on_duplicate(OnDuplicate::Reject);
+
+    assert_eq!(queue.produce((0, 'a')), Ok(()));
+    assert_eq!(
+        queue.produce((0, 'b')),
+        Err(queue.recall(
+            Fault::Insufficiency(DedupInsufficiency::Duplicate(Duplicate::default())),
+            (0, 'b')
+        ))
+    );
+    assert_eq!(queue.produce((1, 'c')), Ok(()));
+    assert_eq!(
+        queue.produce((2, 'd')),
+        Err(queue.recall(
+            Fault::Insufficiency(DedupInsufficiency::Stock(FullStock::default())),
+            (2, 'd')
+        ))
+    );
+    assert_eq!(queue.consume(), Ok((0, 'a')));
+    assert_eq!(queue.produce((0, 'b')), Ok(()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dedup_releases_evicted_keys() {
+    let queue = DedupQueue::new(BoundedQueue::with_overflow(
+        "queue",
+        2,
+        Overflow::DropOldest,
+    ))
+    .release_evicted();
+
+    assert_eq!(queue.produce_all(&mut (0..3)), Ok(()));
+    assert_eq!(queue.produce(0), Ok(()));
+    assert_eq!(queue.consume(), Ok(2));
+    assert_eq!(queue.consume(), Ok(0));
+    assert_eq!(
+        queue.consume(),
+        Err(queue.failure(Fault::Insufficiency(EmptyStock::default())))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dedup_release_evicted_keeps_callback() {
+    use std::sync::{Arc, Mutex};
+
+    let evicted = Arc::new(Mutex::new(Vec::new()));
+    let eviction = Arc::clone(&evicted);
+    let queue = DedupQueue::new(
+        BoundedQueue::with_overflow("queue", 1, Overflow::DropOldest)
+            .evict_with(move |good| eviction.lock().unwrap().push(good)),
+    )
+    .release_evicted();
+
+    assert_eq!(queue.produce_all(&mut (0..2)), Ok(()));
+    assert_eq!(queue.produce(0), Ok(()));
+    assert_eq!(*evicted.lock().unwrap(), vec![0, 1]);
+    assert_eq!(queue.consume(), Ok(0));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dedup_does_not_record_dropped_goods() {
+    let queue = DedupQueue::new(BoundedQueue::with_overflow(
+        "queue",
+        1,
+        Overflow::DropNewest,
+    ))
+    .release_evicted();
+
+    assert_eq!(queue.produce(0), Ok(()));
+    assert_eq!(queue.produce(1), Ok(()));
+    assert_eq!(queue.consume(), Ok(0));
+    assert_eq!(queue.produce(1), Ok(()));
+    assert_eq!(queue.demand(), Ok(1));
+}